@@ -1,18 +1,120 @@
 #![no_std]
 
 const PERCENTAGE_TOTAL: u32 = 10_000; // 100%
-const MINIMUM_DEPOSIT: u64 = 1_000;
+const MIN_PERCENTAGE_BASE: u32 = 100;
+const MAX_PERCENTAGE_BASE: u32 = 1_000_000_000;
+const MAX_FEE_HARD_LIMIT: u32 = 2_000; // 20%, scaled to the percentage base
+const DEFAULT_MINIMUM_DEPOSIT: u64 = 1_000;
+const DEFAULT_MINIMUM_SWAP: u64 = 1_000;
+const RATE_PRECISION: u64 = 1_000_000_000_000;
+const CONTRACT_VERSION: &[u8] = env!("CARGO_PKG_VERSION").as_bytes();
+const DEFAULT_RECENT_SWAPS_CAPACITY: usize = 10;
 
 elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+#[derive(TopEncode, TopDecode, TypeAbi)]
+pub struct ExchangeRate<M: ManagedTypeApi> {
+    pub numerator: BigUint<M>,
+    pub denominator: BigUint<M>,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi)]
+pub struct PendingFee {
+    pub fee_percent: u32,
+    pub effective_at: u64,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct SwapRecord<M: ManagedTypeApi> {
+    pub caller: ManagedAddress<M>,
+    pub token: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub timestamp: u64,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct FeeTier<M: ManagedTypeApi> {
+    pub threshold: BigUint<M>,
+    pub fee_percent: u32,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi)]
+pub struct PendingSwap<M: ManagedTypeApi> {
+    pub caller: ManagedAddress<M>,
+    pub output_token: TokenIdentifier<M>,
+    pub referrer: Option<ManagedAddress<M>>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct SwapLeg<M: ManagedTypeApi> {
+    pub token: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub fee: BigUint<M>,
+    pub amount_out: BigUint<M>,
+}
+
+pub type HarvestRequest<M> = MultiValue3<TokenIdentifier<M>, u64, BigUint<M>>;
+pub type DepositAllocation<M> = MultiValue2<ManagedAddress<M>, BigUint<M>>;
+pub type FeeTierArg<M> = MultiValue2<BigUint<M>, u32>;
+
 #[elrond_wasm::derive::contract]
 pub trait Unlocker {
     #[init]
-    fn init(&self, from_token: TokenIdentifier, to_token: TokenIdentifier, fee_percent: u32) {
+    fn init(
+        &self,
+        from_token: TokenIdentifier,
+        to_token: TokenIdentifier,
+        fee_percent: u32,
+        #[var_args] percentage_base: OptionalValue<u32>,
+    ) {
+        require!(from_token != to_token, "tokens must differ");
+        if self.to_token().is_empty() {
+            // fixed at deploy time, every stored percentage is relative to it
+            let base = percentage_base.into_option().unwrap_or(PERCENTAGE_TOTAL);
+            require!(
+                (MIN_PERCENTAGE_BASE..=MAX_PERCENTAGE_BASE).contains(&base),
+                "Invalid percentage base"
+            );
+            self.percentage_base().set(base);
+        }
+        self.require_valid_fee_percentage(fee_percent);
+
+        if !self.to_token().is_empty() {
+            // upgradeContract re-runs init, keep the existing configuration
+            self.upgrade();
+            return;
+        }
+
         self.try_set_fee_percentage(fee_percent);
 
-        self.add_from_token(from_token);
+        self.add_from_token(from_token, OptionalValue::None);
+
+        self.add_to_token(to_token);
+
+        self.init_new_storage();
+        self.version().set(&ManagedBuffer::from(CONTRACT_VERSION));
+    }
+
+    fn upgrade(&self) {
+        self.init_new_storage();
+        self.version().set(&ManagedBuffer::from(CONTRACT_VERSION));
+    }
+
+    // only fills mappers that are still empty, so it is safe to run on upgrade
+    fn init_new_storage(&self) {
+        // deposits used the swap fee before they had their own
+        self.deposit_fee_percent()
+            .set_if_empty(&self.fee_percent().get());
+        self.minimum_deposit()
+            .set_if_empty(&BigUint::from(DEFAULT_MINIMUM_DEPOSIT));
+        self.minimum_swap()
+            .set_if_empty(&BigUint::from(DEFAULT_MINIMUM_SWAP));
+        self.recent_swaps_capacity()
+            .set_if_empty(&DEFAULT_RECENT_SWAPS_CAPACITY);
 
-        self.add_to_token(to_token)
+        self.fee_recipient()
+            .set_if_empty(&self.blockchain().get_caller());
     }
 
     #[payable("*")]
@@ -21,33 +123,104 @@ pub trait Unlocker {
         &self,
         #[payment_token] token_id: TokenIdentifier,
         #[payment_amount] amount: BigUint,
+        #[var_args] min_amount_out: OptionalValue<BigUint>,
+        #[var_args] referrer: OptionalValue<ManagedAddress>,
+        #[var_args] deadline: OptionalValue<u64>,
     ) -> () {
-        require!(amount >= PERCENTAGE_TOTAL, "amount too small");
-        require!(!self.blockchain().get_caller().is_zero(), "invalid caller");
-        require!(
-            self.from_tokens().contains(&token_id),
-            "token not supported"
-        );
+        self.non_reentrant(|| {
+            if let OptionalValue::Some(deadline) = deadline {
+                require!(
+                    self.blockchain().get_block_timestamp() <= deadline,
+                    "swap expired"
+                );
+            }
+            self.execute_swap(&token_id, &amount, min_amount_out, referrer);
+        })
+    }
+
+    // fills as much as the liquidity allows and refunds the rest of the payment
+    #[payable("*")]
+    #[endpoint(swapPartial)]
+    fn swap_partial(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: BigUint,
+        #[var_args] min_amount_out: OptionalValue<BigUint>,
+    ) {
+        self.non_reentrant(|| {
+            let max_fill = self.get_max_swapable_amount(token_id.clone());
+            let fill = core::cmp::min(amount.clone(), max_fill);
 
-        let fee_percent = self.fee_percent().get();
-        require!(&fee_percent > &0, "zero fee");
+            // refund first, an async output ends the call
+            let refund = &amount - &fill;
+            if refund > 0 {
+                let caller = self.blockchain().get_caller();
+                self.send().direct(&caller, &token_id, 0, &refund, &[]);
+            }
 
-        let fee = self.calculate_percentage(&amount, &fee_percent);
-        let amount_after_fee = &amount - &fee;
+            self.execute_swap(&token_id, &fill, min_amount_out, OptionalValue::None);
+        })
+    }
 
-        require!(&amount_after_fee < &amount, "incorrect fee");
-        require!(
-            &amount_after_fee <= &self.get_liquidity_balance(),
-            "no liquidity"
-        );
-        require!(&amount_after_fee > &0, "nothing to send");
-        self.send().direct(
-            &self.blockchain().get_caller(),
-            &self.to_token().get(),
-            0,
-            &amount_after_fee,
-            &[],
-        );
+    // every payment must be a supported from token with the same output token,
+    // the outputs are summed and sent in a single transfer
+    #[payable("*")]
+    #[endpoint(swapMulti)]
+    fn swap_multi(&self, #[var_args] min_amount_out: OptionalValue<BigUint>) {
+        self.non_reentrant(|| {
+            let payments = self.call_value().all_esdt_transfers();
+            require!(!payments.is_empty(), "no payments");
+
+            let caller = self.require_can_swap();
+            let output_async = self.output_gas_limit().get() > 0;
+            let swap_id = if output_async {
+                self.next_pending_swap_id()
+            } else {
+                0
+            };
+            let mut output_token: Option<TokenIdentifier> = None;
+            let mut total_out = BigUint::zero();
+            for payment in payments.iter() {
+                let (payment_output, fee, amount_out) =
+                    self.quote_swap(&payment.token_identifier, &payment.amount);
+                if let Some(token) = &output_token {
+                    require!(
+                        token == &payment_output,
+                        "payments must share an output token"
+                    );
+                }
+                total_out += &amount_out;
+                let leg = SwapLeg {
+                    token: payment.token_identifier,
+                    amount: payment.amount,
+                    fee,
+                    amount_out,
+                };
+                if output_async {
+                    self.defer_swap(swap_id, &leg);
+                } else {
+                    self.book_swap(&caller, &leg, &payment_output, &None);
+                    self.swap_leg_event(&caller, &leg, &payment_output);
+                }
+                output_token = Some(payment_output);
+            }
+
+            let output_token = output_token.unwrap();
+            require!(
+                total_out <= self.blockchain().get_sc_balance(&output_token, 0),
+                "no liquidity"
+            );
+            if let OptionalValue::Some(min_out) = min_amount_out {
+                require!(total_out >= min_out, "slippage exceeded");
+            }
+
+            if output_async {
+                self.send_swap_output_async(swap_id, &caller, &output_token, &total_out, None);
+            }
+            self.send()
+                .direct(&caller, &output_token, 0, &total_out, &[]);
+            self.record_liquidity_change();
+        })
     }
 
     #[payable("*")]
@@ -55,21 +228,82 @@ pub trait Unlocker {
     fn deposit(
         &self,
         #[payment_token] token_id: TokenIdentifier,
+        #[payment_nonce] nonce: u64,
         #[payment_amount] amount: BigUint,
     ) -> () {
-        let caller = self.blockchain().get_caller();
-        require!(!caller.is_zero(), "invalid caller");
-        require!(self.to_token().get() == token_id, "token not supported");
-        require!(amount > 0, "incorrect amount");
-        require!(
-            amount >= MINIMUM_DEPOSIT,
-            "Deposit amount must be greater than or equal to minimum deposit"
-        );
+        self.non_reentrant(|| {
+            let caller = self.blockchain().get_caller();
+            require!(!caller.is_zero(), "invalid caller");
+            self.require_not_blacklisted(&caller);
+
+            self.credit_deposit(&caller, &token_id, nonce, &amount, None);
+        })
+    }
+
+    #[payable("*")]
+    #[endpoint(depositFor)]
+    fn deposit_for(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_nonce] nonce: u64,
+        #[payment_amount] amount: BigUint,
+        beneficiary: ManagedAddress,
+    ) {
+        self.non_reentrant(|| {
+            let caller = self.blockchain().get_caller();
+            require!(!caller.is_zero(), "invalid caller");
+            require!(!beneficiary.is_zero(), "invalid beneficiary");
+            self.require_not_blacklisted(&caller);
+            self.require_not_blacklisted(&beneficiary);
 
-        let amount_with_fees = self.calculate_amount_with_fees(&amount);
+            self.credit_deposit(&beneficiary, &token_id, nonce, &amount, None);
+        })
+    }
 
-        self.depositor_balance(&caller)
-            .update(|balance| *balance += &amount_with_fees);
+    #[payable("*")]
+    #[endpoint(depositBatch)]
+    fn deposit_batch(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_nonce] nonce: u64,
+        #[payment_amount] amount: BigUint,
+        #[var_args] allocations: MultiValueEncoded<DepositAllocation<Self::Api>>,
+    ) {
+        self.non_reentrant(|| {
+            let caller = self.blockchain().get_caller();
+            require!(!caller.is_zero(), "invalid caller");
+            self.require_not_blacklisted(&caller);
+            require!(!allocations.is_empty(), "no allocations");
+
+            let mut allocated = BigUint::zero();
+            for allocation in allocations.into_iter() {
+                let (beneficiary, portion) = allocation.into_tuple();
+                require!(!beneficiary.is_zero(), "invalid beneficiary");
+                self.require_not_blacklisted(&beneficiary);
+
+                self.credit_deposit(&beneficiary, &token_id, nonce, &portion, None);
+                allocated += portion;
+            }
+            require!(allocated == amount, "allocations do not match payment");
+        })
+    }
+
+    #[payable("*")]
+    #[endpoint(depositWithRate)]
+    fn deposit_with_rate(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_nonce] nonce: u64,
+        #[payment_amount] amount: BigUint,
+    ) {
+        self.non_reentrant(|| {
+            let caller = self.blockchain().get_caller();
+            require!(!caller.is_zero(), "invalid caller");
+            self.require_not_blacklisted(&caller);
+
+            let scaled_rate = self.scaled_exchange_rate();
+            self.credit_deposit(&caller, &token_id, nonce, &amount, Some(scaled_rate));
+        })
     }
 
     #[view(getLiquidityBalance)]
@@ -77,94 +311,2242 @@ pub trait Unlocker {
         self.blockchain().get_sc_balance(&self.to_token().get(), 0)
     }
 
+    #[view(getTokenBalance)]
+    fn get_token_balance(&self, token: TokenIdentifier, nonce: u64) -> BigUint {
+        self.blockchain().get_sc_balance(&token, nonce)
+    }
+
+    // lets callers check burnFees will go through
+    #[view(hasLocalBurnRole)]
+    fn has_local_burn_role(&self, token: TokenIdentifier) -> bool {
+        self.blockchain()
+            .get_esdt_local_roles(&token)
+            .has_role(&EsdtLocalRole::Burn)
+    }
+
+    // deposits mint receipts, so receipts need this role on the receipt token
+    #[view(hasLocalMintRole)]
+    fn has_local_mint_role(&self, token: TokenIdentifier) -> bool {
+        self.blockchain()
+            .get_esdt_local_roles(&token)
+            .has_role(&EsdtLocalRole::Mint)
+    }
+
+    #[view(getContractState)]
+    fn get_contract_state(&self) -> MultiValue4<BigUint, TokenIdentifier, BigUint, bool> {
+        (
+            self.fee_percent().get(),
+            self.to_token().get(),
+            self.get_liquidity_balance(),
+            self.is_paused(),
+        )
+            .into()
+    }
+
+    // outstanding depositor credit against the to_token liquidity
+    #[view(getProtocolHealth)]
+    fn get_protocol_health(&self) -> MultiValue3<BigUint, BigUint, bool> {
+        let total_deposited = self.total_deposited().get();
+        let liquidity = self.get_liquidity_balance();
+        let is_solvent = liquidity >= total_deposited;
+        (total_deposited, liquidity, is_solvent).into()
+    }
+
+    // first from token, see getFromTokens for the rest
+    #[view(getInitParameters)]
+    fn get_init_parameters(&self) -> MultiValue3<TokenIdentifier, TokenIdentifier, u32> {
+        let from_token = self.from_tokens().iter().next();
+        // EGLD can be a from token, so it cannot stand in for an empty set
+        require!(from_token.is_some(), "no from token configured");
+        let from_token = from_token.unwrap();
+        let fee_percent = self.fee_percent().get().to_u64().unwrap_or_default() as u32;
+        (from_token, self.to_token().get(), fee_percent).into()
+    }
+
+    #[view(getDepositors)]
+    fn get_depositors(
+        &self,
+        from: usize,
+        size: usize,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for address in self.depositors().iter().skip(from).take(size) {
+            let balance = self.depositor_balance(&address).get();
+            result.push((address, balance).into());
+        }
+        result
+    }
+
+    // oldest first
+    #[view(getRecentSwaps)]
+    fn get_recent_swaps(&self) -> MultiValueEncoded<SwapRecord<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let len = self.recent_swaps().len();
+        let next = self.recent_swaps_next().get();
+        for i in 0..len {
+            result.push(self.recent_swaps().get((next + i) % len + 1));
+        }
+        result
+    }
+
+    // tier applied to a swap of `amount` when the token has no fee override
+    #[view(getTierForAmount)]
+    fn get_tier_for_amount(&self, amount: BigUint) -> MultiValue2<BigUint, u32> {
+        let tier = self.global_fee_tier(&amount);
+        (tier.threshold, tier.fee_percent).into()
+    }
+
+    #[view(isFromTokenSupported)]
+    fn is_from_token_supported(&self, token: TokenIdentifier) -> bool {
+        self.from_tokens().contains(&token)
+    }
+
+    #[view(getWithdrawableByOwner)]
+    fn get_withdrawable_by_owner(&self, token: TokenIdentifier, nonce: u64) -> BigUint {
+        let sc_balance = self.get_unreserved_balance(&token, nonce);
+        let reserve = self.min_reserve(&token).get();
+        if sc_balance > reserve {
+            sc_balance - reserve
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    #[view(getDepositorCount)]
+    fn get_depositor_count(&self) -> usize {
+        self.depositors().len()
+    }
+
+    #[view(getSwapQuote)]
+    fn get_swap_quote(
+        &self,
+        token_id: TokenIdentifier,
+        amount: BigUint,
+    ) -> MultiValue3<BigUint, BigUint, BigUint> {
+        require!(
+            self.from_tokens().contains(&token_id),
+            "token not supported"
+        );
+        self.calculate_swap(&token_id, &amount).into()
+    }
+
+    // smallest deposit whose credit reaches the target; when the fee rounding
+    // skips the exact target the credit overshoots it by one unit
+    #[view(getRequiredDepositForTarget)]
+    fn get_required_deposit_for_target(&self, target_credit: BigUint) -> BigUint {
+        let total = BigUint::from(self.get_percentage_base());
+        let divisor = &total + &self.deposit_fee_percent().get();
+        (target_credit * total + &divisor - 1u32) / divisor
+    }
+
+    // at the base fee; token fees, tiers, the minimum fee and per-swap
+    // rounding can make the actual consumption differ by a few units
+    #[view(getRequiredLiquidityForVolume)]
+    fn get_required_liquidity_for_volume(&self, gross_volume: BigUint) -> BigUint {
+        let fee = self.calculate_swap_fee(&gross_volume, &self.fee_percent().get());
+        self.apply_exchange_rate(&(&gross_volume - &fee))
+    }
+
+    // balance of `token` nothing is attributed to: not its fees, referral
+    // earnings or pending swap payments, nor depositor credit, which is owed
+    // in the to token; zero when the attributed part exceeds the balance
+    #[view(getDust)]
+    fn get_dust(&self, token: &TokenIdentifier) -> BigUint {
+        let sc_balance = self.blockchain().get_sc_balance(token, 0);
+        let mut attributed = self.accumulated_fees(token).get()
+            + self.referral_outstanding(token).get()
+            + self.pending_swap_amount(token).get();
+        if token == &self.to_token().get() {
+            attributed += self.total_deposited().get();
+        }
+        if sc_balance > attributed {
+            sc_balance - attributed
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    // dry run of the swap preconditions that do not depend on the caller;
+    // blacklist, allowlist, deadline and slippage are not covered
+    #[view(canSwap)]
+    fn can_swap(&self, token_id: TokenIdentifier, amount: BigUint) -> bool {
+        if self.swap_paused().get() || amount < self.minimum_swap().get() {
+            return false;
+        }
+        let max_swap = self.max_swap().get();
+        if max_swap > 0 && amount > max_swap {
+            return false;
+        }
+        if !self.from_tokens().contains(&token_id) {
+            return false;
+        }
+        let output_token = self.get_output_token(&token_id);
+        if token_id == output_token {
+            return false;
+        }
+
+        let (fee, amount_after_fee, amount_out) = self.calculate_swap(&token_id, &amount);
+        fee > 0
+            && amount_after_fee < amount
+            && amount_after_fee > 0
+            && amount_out <= self.blockchain().get_sc_balance(&output_token, 0)
+            && amount_out > 0
+    }
+
+    // largest input whose output still fits in the output token balance,
+    // working back through the exchange rate and the rounded-down fee
+    #[view(getMaxSwapableAmount)]
+    fn get_max_swapable_amount(&self, token_id: TokenIdentifier) -> BigUint {
+        require!(
+            self.from_tokens().contains(&token_id),
+            "token not supported"
+        );
+        let output_token = self.get_output_token(&token_id);
+        let liquidity = self.blockchain().get_sc_balance(&output_token, 0);
+        let rate = self.current_exchange_rate();
+        let max_after_fee = ((liquidity + 1u32) * &rate.denominator - 1u32) / &rate.numerator;
+
+        // each fee tier covers [threshold, next threshold), keep the largest
+        // input that still lands inside the tier whose fee it is charged
+        let mut max_amount = BigUint::zero();
+        let tier_count = self.fee_schedule_len(&token_id);
+        for index in 0..tier_count {
+            let tier = self.fee_schedule_tier(&token_id, index);
+            let mut candidate = match self.max_input_for_fee(&max_after_fee, tier.fee_percent) {
+                Some(candidate) if candidate >= tier.threshold => candidate,
+                _ => continue,
+            };
+            if index + 1 < tier_count {
+                let upper = self.fee_schedule_tier(&token_id, index + 1).threshold;
+                if candidate >= upper {
+                    candidate = upper - 1u32;
+                }
+            }
+            if candidate > max_amount {
+                max_amount = candidate;
+            }
+        }
+
+        let max_swap = self.max_swap().get();
+        if max_swap > 0 && max_amount > max_swap {
+            return max_swap;
+        }
+        max_amount
+    }
+
+    // true while any of the endpoint groups is paused
+    #[view(isPaused)]
+    fn is_paused(&self) -> bool {
+        self.swap_paused().get() || self.deposit_paused().get() || self.harvest_paused().get()
+    }
+
+    // breakdown for the global swap fee, token overrides are not applied;
+    // the rates are in parts of the percentage base
+    #[view(getFeeBreakdown)]
+    fn get_fee_breakdown(&self, amount: BigUint) -> MultiValue4<u32, BigUint, u32, BigUint> {
+        let swap_fee = self.fee_percent().get();
+        let deposit_fee = self.deposit_fee_percent().get();
+        // both are validated against the percentage base so they fit in a u32
+        let swap_rate = swap_fee.to_u64().unwrap_or_default() as u32;
+        let deposit_rate = deposit_fee.to_u64().unwrap_or_default() as u32;
+        (
+            swap_rate,
+            self.calculate_swap_fee(&amount, &swap_fee),
+            deposit_rate,
+            self.calculate_percentage(&amount, &deposit_fee),
+        )
+            .into()
+    }
+
+    // falls back to the hard limit until the owner lowers it
+    #[view(getMaxFee)]
+    fn get_max_fee(&self) -> u32 {
+        if self.max_fee().is_empty() {
+            return self.get_max_fee_hard_limit();
+        }
+        self.max_fee().get()
+    }
+
+    #[view(getMaxFeeHardLimit)]
+    fn get_max_fee_hard_limit(&self) -> u32 {
+        let base = self.get_percentage_base() as u64;
+        (base * MAX_FEE_HARD_LIMIT as u64 / PERCENTAGE_TOTAL as u64) as u32
+    }
+
+    // contracts deployed before the base was configurable use PERCENTAGE_TOTAL
+    #[view(getPercentageBase)]
+    fn get_percentage_base(&self) -> u32 {
+        if self.percentage_base().is_empty() {
+            return PERCENTAGE_TOTAL;
+        }
+        self.percentage_base().get()
+    }
+
+    #[view(getDepositorBalanceInToToken)]
+    fn get_depositor_balance_in_to_token(&self, address: ManagedAddress) -> BigUint {
+        self.apply_exchange_rate(&self.get_harvestable_credit(&address))
+    }
+
+    // share of the total deposited credit, in parts of the percentage base
+    #[view(getDepositorShare)]
+    fn get_depositor_share(&self, address: ManagedAddress) -> BigUint {
+        let total = self.total_deposited().get();
+        if total == 0 {
+            return BigUint::zero();
+        }
+        self.depositor_balance(&address).get() * self.get_percentage_base() / total
+    }
+
+    #[view(getPendingFee)]
+    fn get_pending_fee(&self) -> MultiValue2<u32, u64> {
+        if self.pending_fee().is_empty() {
+            return (0, 0).into();
+        }
+        let pending = self.pending_fee().get();
+        (pending.fee_percent, pending.effective_at).into()
+    }
+
+    #[view(getHarvestableAmount)]
+    fn get_harvestable_amount(
+        &self,
+        address: ManagedAddress,
+        token: TokenIdentifier,
+        nonce: u64,
+    ) -> BigUint {
+        if !self.from_tokens().contains(&token) || (nonce != 0 && !self.nft_token(&token).get()) {
+            return BigUint::zero();
+        }
+        let dep_balance = self.get_harvestable_credit(&address);
+        let sc_balance = self.get_harvestable_balance(&token, nonce);
+        core::cmp::min(dep_balance, sc_balance)
+    }
+
+    #[view(getTimeUntilNextHarvest)]
+    fn get_time_until_next_harvest(&self, address: ManagedAddress) -> u64 {
+        let cooldown = self.harvest_cooldown().get();
+        let last_harvest = self.last_harvest(&address).get();
+        if cooldown == 0 || last_harvest == 0 {
+            return 0;
+        }
+        (last_harvest + cooldown).saturating_sub(self.blockchain().get_block_timestamp())
+    }
+
+    // a pending fee only counts once applyFee has promoted it; the amount
+    // tiers are only resolved when the swap amount is given
+    #[view(getEffectiveFee)]
+    fn get_effective_fee(
+        &self,
+        token: TokenIdentifier,
+        #[var_args] amount: OptionalValue<BigUint>,
+    ) -> u32 {
+        if let OptionalValue::Some(amount) = amount {
+            return self.get_fee_tier(&token, &amount).fee_percent;
+        }
+        // the global fee is validated against the percentage base so it fits in a u32
+        self.get_swap_fee_percent(&token)
+            .to_u64()
+            .unwrap_or_default() as u32
+    }
+
+    #[view(getFromTokensWithFees)]
+    fn get_from_tokens_with_fees(&self) -> MultiValueEncoded<MultiValue2<TokenIdentifier, u32>> {
+        let mut result = MultiValueEncoded::new();
+        for token in self.from_tokens().iter() {
+            let fee = self.get_effective_fee(token.clone(), OptionalValue::None);
+            result.push((token, fee).into());
+        }
+        result
+    }
+
+    // non-zero accrued fees of every from token followed by the to token
+    #[view(getAllAccumulatedFees)]
+    fn get_all_accumulated_fees(&self) -> MultiValueEncoded<MultiValue2<TokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        let to_token = self.to_token().get();
+        let from_tokens = self.from_tokens();
+        for token in from_tokens.iter().chain(core::iter::once(to_token)) {
+            let fees = self.accumulated_fees(&token).get();
+            if fees > 0 {
+                result.push((token, fees).into());
+            }
+        }
+        result
+    }
+
+    // fees claimed or burned between the two snapshots shrink the delta
+    #[view(getFeeRevenueBetween)]
+    fn get_fee_revenue_between(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+        token: TokenIdentifier,
+    ) -> BigUint {
+        require!(
+            from_epoch <= to_epoch && to_epoch < self.fee_snapshot_epoch().get(),
+            "invalid snapshot range"
+        );
+        let from_fees = self.fee_snapshot(from_epoch, &token).get();
+        let to_fees = self.fee_snapshot(to_epoch, &token).get();
+        if to_fees > from_fees {
+            to_fees - from_fees
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    #[view(getContractOwner)]
+    fn get_contract_owner(&self) -> ManagedAddress {
+        self.get_effective_owner()
+    }
+
+    #[view(getPendingOwner)]
+    fn get_pending_owner(&self) -> OptionalValue<ManagedAddress> {
+        if self.pending_owner().is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.pending_owner().get())
+    }
+
+    #[view(getBalanceBatch)]
+    fn get_balance_batch(
+        &self,
+        #[var_args] addresses: MultiValueEncoded<ManagedAddress>,
+    ) -> MultiValueEncoded<BigUint> {
+        let mut result = MultiValueEncoded::new();
+        for address in addresses.into_iter() {
+            result.push(self.depositor_balance(&address).get());
+        }
+        result
+    }
+
+    #[view(getOutputToken)]
+    fn get_output_token(&self, from_token: &TokenIdentifier) -> TokenIdentifier {
+        let output_mapper = self.output_for(from_token);
+        if output_mapper.is_empty() {
+            return self.to_token().get();
+        }
+        output_mapper.get()
+    }
+
+    #[view(getExchangeRate)]
+    fn get_exchange_rate(&self) -> MultiValue2<BigUint, BigUint> {
+        let rate = self.current_exchange_rate();
+        (rate.numerator, rate.denominator).into()
+    }
+
+    #[payable("*")]
     #[endpoint(harvest)]
     fn harvest(
         &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
         token: TokenIdentifier,
         nonce: u64,
         amount: BigUint,
     ) -> () {
+        self.non_reentrant(|| {
+            require!(!self.harvest_paused().get(), "harvest is paused");
+            let caller = self.blockchain().get_caller();
+            require!(!caller.is_zero(), "invalid caller");
+            self.require_not_blacklisted(&caller);
+            self.require_harvest_cooldown_elapsed(&caller);
+
+            let credit_used = self.harvest_for(&caller, &token, nonce, &amount);
+            self.settle_receipt(&payment_token, &payment_amount, &credit_used);
+
+            self.last_harvest(&caller)
+                .set(&self.blockchain().get_block_timestamp());
+        })
+    }
+
+    #[payable("*")]
+    #[endpoint(harvestMulti)]
+    fn harvest_multi(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] requests: MultiValueEncoded<HarvestRequest<Self::Api>>,
+    ) {
+        self.non_reentrant(|| {
+            require!(!self.harvest_paused().get(), "harvest is paused");
+            let caller = self.blockchain().get_caller();
+            require!(!caller.is_zero(), "invalid caller");
+            self.require_not_blacklisted(&caller);
+            self.require_harvest_cooldown_elapsed(&caller);
+
+            let mut credit_used = BigUint::zero();
+            for request in requests.into_iter() {
+                let (token, nonce, amount) = request.into_tuple();
+                credit_used += self.harvest_for(&caller, &token, nonce, &amount);
+            }
+            self.settle_receipt(&payment_token, &payment_amount, &credit_used);
+
+            self.last_harvest(&caller)
+                .set(&self.blockchain().get_block_timestamp());
+        })
+    }
+
+    #[endpoint(approveHarvest)]
+    fn approve_harvest(&self, spender: ManagedAddress, amount: BigUint) {
         let caller = self.blockchain().get_caller();
-        require!(!caller.is_zero(), "invalid caller");
+        require!(!spender.is_zero(), "invalid spender");
+        require!(spender != caller, "cannot approve yourself");
+        self.harvest_allowance(&caller, &spender).set(&amount);
+    }
+
+    // the allowance is spent in depositor credit, the harvested tokens go to the owner
+    #[endpoint(harvestFrom)]
+    fn harvest_from(
+        &self,
+        owner: ManagedAddress,
+        token: TokenIdentifier,
+        nonce: u64,
+        amount: BigUint,
+    ) {
+        self.non_reentrant(|| {
+            require!(!self.harvest_paused().get(), "harvest is paused");
+            let caller = self.blockchain().get_caller();
+            require!(!caller.is_zero(), "invalid caller");
+            self.require_not_blacklisted(&caller);
+            self.require_not_blacklisted(&owner);
+            self.require_harvest_cooldown_elapsed(&owner);
+            require!(
+                !self.receipt_active(),
+                "receipts must be returned by the depositor"
+            );
+
+            let credit_cost = self.harvest_credit_cost(&owner, &amount);
+            require!(
+                credit_cost <= self.harvest_allowance(&owner, &caller).get(),
+                "harvest above allowance"
+            );
+            self.harvest_allowance(&owner, &caller)
+                .update(|allowance| *allowance -= &credit_cost);
+            self.harvest_for(&owner, &token, nonce, &amount);
+
+            self.last_harvest(&owner)
+                .set(&self.blockchain().get_block_timestamp());
+        })
+    }
+
+    #[payable("*")]
+    #[endpoint(reclaimDeposit)]
+    fn reclaim_deposit(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        amount: BigUint,
+    ) {
+        self.non_reentrant(|| {
+            require!(!self.harvest_paused().get(), "harvest is paused");
+            let caller = self.blockchain().get_caller();
+            require!(!caller.is_zero(), "invalid caller");
+            self.require_not_blacklisted(&caller);
 
-        let sc_balance = self.blockchain().get_sc_balance(&token, nonce);
-        let dep_balance = self.depositor_balance(&caller).get();
+            let dep_balance = self.depositor_balance(&caller).get();
 
-        require!(self.from_tokens().contains(&token), "token not supported");
-        require!(&amount > &0, "Invalid amount");
-        require!(&sc_balance > &0, "Insufficient contract funds (0)");
-        require!(&dep_balance > &0, "Insufficient depositor funds (0)");
-        require!(&sc_balance >= &amount, "Insufficient sc funds");
-        require!(&dep_balance >= &amount, "Insufficient depositor funds");
+            require!(amount > 0, "Invalid amount");
+            require!(dep_balance >= amount, "Insufficient depositor funds");
+            self.settle_receipt(&payment_token, &payment_amount, &amount);
 
-        self.send().direct(&caller, &token, nonce, &amount, &[]);
+            // the credit is paid back as the principal it stands for, the
+            // deposit fee part of it was never paid in
+            let principal = self.debit_credit(&caller, &amount);
+            require!(principal > 0, "nothing to reclaim");
+            require!(principal <= self.get_liquidity_balance(), "no liquidity");
+            let remaining_balance = self.depositor_balance(&caller).get();
 
-        self.depositor_balance(&caller)
-            .update(|balance| *balance -= &amount);
+            let to_token = self.to_token().get();
+            self.send().direct(&caller, &to_token, 0, &principal, &[]);
+
+            self.reclaim_deposit_event(&caller, &to_token, &principal, &remaining_balance);
+            self.record_liquidity_change();
+        })
     }
 
-    // PRIVATE METHODS
-    fn calculate_percentage(&self, total_amount: &BigUint, percentage: &BigUint) -> BigUint {
-        total_amount * percentage / PERCENTAGE_TOTAL
+    #[endpoint(closeAccount)]
+    fn close_account(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.depositor_balance(&caller).get() == 0,
+            "depositor balance not zero"
+        );
+
+        self.depositor_balance(&caller).clear();
+        self.depositor_principal(&caller).clear();
+        self.depositor_rate(&caller).clear();
+        self.depositors().remove(&caller);
     }
-    fn calculate_amount_with_fees(&self, amount: &BigUint) -> BigUint {
-        let fee_percent = self.fee_percent().get();
-        let fee = self.calculate_percentage(&amount, &fee_percent);
 
-        amount + &fee
+    #[endpoint(claimFees)]
+    fn claim_fees(&self, token: TokenIdentifier) {
+        let fees = self.accumulated_fees(&token).get();
+        require!(fees > 0, "no fees to claim");
+
+        self.accumulated_fees(&token).clear();
+        self.send()
+            .direct(&self.fee_recipient().get(), &token, 0, &fees, &[]);
+        // to token fees are part of the liquidity since rounding dust accrues to them
+        self.record_liquidity_change();
     }
 
-    // OWNER ENDPOINTS
+    // needs the ESDTRoleLocalBurn role, without it the burn fails and the call reverts
+    #[endpoint(burnFees)]
+    fn burn_fees(&self, token: TokenIdentifier) {
+        self.require_owner();
+        let fees = self.accumulated_fees(&token).get();
+        require!(fees > 0, "no fees to burn");
 
-    #[only_owner]
-    #[endpoint(addFromToken)]
-    fn add_from_token(&self, asset: TokenIdentifier) -> () {
-        require!(asset.is_valid_esdt_identifier(), "Invalid ESDT");
-        self.from_tokens().insert(asset);
+        self.accumulated_fees(&token).clear();
+        self.send().esdt_local_burn(&token, 0, &fees);
+        self.record_liquidity_change();
     }
 
-    #[only_owner]
-    #[endpoint(setToToken)]
-    fn add_to_token(&self, asset: TokenIdentifier) -> () {
-        require!(asset.is_valid_esdt_identifier(), "Invalid ESDT");
-        self.to_token().set(&asset);
+    #[endpoint(claimReferral)]
+    fn claim_referral(&self, token: TokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+        let earned = self.referral_earned(&caller, &token).get();
+        require!(earned > 0, "nothing to claim");
+
+        self.referral_earned(&caller, &token).clear();
+        self.referral_outstanding(&token)
+            .update(|outstanding| *outstanding -= &earned);
+        self.send().direct(&caller, &token, 0, &earned, &[]);
     }
 
-    #[only_owner]
-    #[endpoint(setFee)]
-    fn try_set_fee_percentage(&self, new_fee_percentage: u32) {
+    // PRIVATE METHODS
+    fn credit_deposit(
+        &self,
+        depositor: &ManagedAddress,
+        token_id: &TokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+        locked_rate: Option<BigUint>,
+    ) {
+        require!(!self.deposit_paused().get(), "deposit is paused");
+        require!(&self.to_token().get() == token_id, "token not supported");
+        require!(nonce == 0, "only fungible to_token");
+        require!(amount > &0, "incorrect amount");
         require!(
-            new_fee_percentage > 0 && new_fee_percentage < PERCENTAGE_TOTAL,
-            "Invalid percentage value, should be between 0 and 10,000"
+            amount >= &self.minimum_deposit().get(),
+            "Deposit amount must be greater than or equal to minimum deposit"
         );
-        self.fee_percent().set(&BigUint::from(new_fee_percentage));
-    }
 
-    #[only_owner]
-    #[endpoint(withdraw)]
-    fn withdraw(&self, token: TokenIdentifier, nonce: u64) -> () {
-        self.send().direct(
-            &self.blockchain().get_owner_address(),
-            &token,
-            nonce,
-            &self.blockchain().get_sc_balance(&token, nonce),
-            &[],
+        let amount_with_fees = self.calculate_amount_with_fees(amount);
+        let max_deposit_credit = self.max_deposit_credit().get();
+        require!(
+            max_deposit_credit == 0 || amount_with_fees <= max_deposit_credit,
+            "deposit credit above ceiling"
         );
-    }
+        self.require_within_max_depositor_balance(depositor, &amount_with_fees);
 
-    // STORAGE
-    #[view(getFee)]
-    #[storage_mapper("fee_percent")]
-    fn fee_percent(&self) -> SingleValueMapper<BigUint>;
+        let old_balance = self.depositor_balance(depositor).get();
+        self.depositor_balance(depositor)
+            .update(|balance| *balance += &amount_with_fees);
+        self.depositor_principal(depositor)
+            .update(|principal| *principal += amount);
+        self.depositors().insert(depositor.clone());
+        self.total_deposited()
+            .update(|total| *total += &amount_with_fees);
 
-    #[storage_mapper("to_token")]
-    fn to_token(&self) -> SingleValueMapper<TokenIdentifier>;
+        // plain deposits leave unlocked credit floating at the current rate,
+        // into a locked rate they blend at the current rate
+        if locked_rate.is_some() || !self.depositor_rate(depositor).is_empty() {
+            let new_rate = locked_rate.unwrap_or_else(|| self.scaled_exchange_rate());
+            self.blend_depositor_rate(depositor, &old_balance, &amount_with_fees, &new_rate);
+        }
 
-    #[view(getFromTokens)]
-    #[storage_mapper("from_tokens")]
-    fn from_tokens(&self) -> SetMapper<TokenIdentifier>;
+        if self.receipt_active() {
+            let receipt_token = self.receipt_token().get();
+            self.send()
+                .esdt_local_mint(&receipt_token, 0, &amount_with_fees);
+            self.send()
+                .direct(depositor, &receipt_token, 0, &amount_with_fees, &[]);
+        }
 
-    #[view(getBalance)]
-    #[storage_mapper("depositor_balance")]
-    fn depositor_balance(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+        self.deposit_event(depositor, token_id, amount, &amount_with_fees);
+        self.record_liquidity_change();
+    }
+    // tracks the depth of the to token for LPs, the mark never decreases
+    fn record_liquidity_change(&self) {
+        let liquidity = self.get_liquidity_balance();
+        if liquidity > self.liquidity_high_water_mark().get() {
+            self.liquidity_high_water_mark().set(&liquidity);
+        }
+        self.liquidity_changed_event(&self.to_token().get(), &liquidity);
+    }
+    // the balance minus payments of swaps still waiting on their output callback
+    fn get_unreserved_balance(&self, token: &TokenIdentifier, nonce: u64) -> BigUint {
+        let sc_balance = self.blockchain().get_sc_balance(token, nonce);
+        if nonce != 0 {
+            return sc_balance;
+        }
+        let pending = self.pending_swap_amount(token).get();
+        if sc_balance > pending {
+            sc_balance - pending
+        } else {
+            BigUint::zero()
+        }
+    }
+    // harvests leave fees and referral earnings to their recipients and
+    // pending swap payments to the swappers
+    fn get_harvestable_balance(&self, token: &TokenIdentifier, nonce: u64) -> BigUint {
+        let available = self.get_unreserved_balance(token, nonce);
+        if nonce != 0 {
+            return available;
+        }
+        let attributed =
+            self.accumulated_fees(token).get() + self.referral_outstanding(token).get();
+        if available > attributed {
+            available - attributed
+        } else {
+            BigUint::zero()
+        }
+    }
+    fn insert_from_token(
+        &self,
+        asset: TokenIdentifier,
+        output_token: OptionalValue<TokenIdentifier>,
+    ) {
+        require!(
+            self.to_token().is_empty() || self.to_token().get() != asset,
+            "from and to token must differ"
+        );
+        if let OptionalValue::Some(output) = output_token {
+            require!(output.is_valid_esdt_identifier(), "Invalid ESDT");
+            require!(output != asset, "from and to token must differ");
+            self.output_for(&asset).set(&output);
+        }
+        self.require_from_tokens_capacity(&asset);
+        self.from_tokens().insert(asset);
+    }
+    // tokens the contract holds on purpose: swap inputs and outputs and receipts
+    fn is_contract_token(&self, token: &TokenIdentifier) -> bool {
+        if token == &self.to_token().get() || self.from_tokens().contains(token) {
+            return true;
+        }
+        if !self.receipt_token().is_empty() && token == &self.receipt_token().get() {
+            return true;
+        }
+        self.from_tokens()
+            .iter()
+            .any(|from_token| token == &self.get_output_token(&from_token))
+    }
+    fn receipt_active(&self) -> bool {
+        self.receipt_enabled().get() && !self.receipt_token().is_empty()
+    }
+    // burns the receipts handed back for the credit consumed by the call
+    fn settle_receipt(
+        &self,
+        payment_token: &TokenIdentifier,
+        payment_amount: &BigUint,
+        credit_used: &BigUint,
+    ) {
+        if !self.receipt_active() {
+            require!(payment_amount == &0, "unexpected payment");
+            return;
+        }
+        let receipt_token = self.receipt_token().get();
+        require!(
+            payment_token == &receipt_token && payment_amount == credit_used,
+            "receipt must be returned"
+        );
+        self.send().esdt_local_burn(&receipt_token, 0, credit_used);
+    }
+    // credit weighted average of the locked rates, scaled by RATE_PRECISION
+    fn blend_depositor_rate(
+        &self,
+        depositor: &ManagedAddress,
+        old_balance: &BigUint,
+        credit: &BigUint,
+        rate: &BigUint,
+    ) {
+        let old_rate = self.get_depositor_rate(depositor);
+        let blended = (old_balance * &old_rate + credit * rate) / (old_balance + credit);
+        self.depositor_rate(depositor).set(&blended);
+    }
+    // the locked rate, or the current rate for credit that is not locked
+    fn get_depositor_rate(&self, depositor: &ManagedAddress) -> BigUint {
+        if self.depositor_rate(depositor).is_empty() {
+            return self.scaled_exchange_rate();
+        }
+        self.depositor_rate(depositor).get()
+    }
+    // from tokens the credit harvests at the depositor's rate
+    fn get_harvestable_credit(&self, depositor: &ManagedAddress) -> BigUint {
+        let balance = self.depositor_balance(depositor).get();
+        balance * RATE_PRECISION / self.get_depositor_rate(depositor)
+    }
+    // credit consumed by harvesting `amount`, rounded up in favour of the pool
+    fn harvest_credit_cost(&self, depositor: &ManagedAddress, amount: &BigUint) -> BigUint {
+        let rate = self.get_depositor_rate(depositor);
+        (amount * &rate + RATE_PRECISION - 1u64) / RATE_PRECISION
+    }
+    // debits credit and returns the part of the principal it stood for, the
+    // rest of the principal stays in proportion to the remaining credit
+    fn debit_credit(&self, depositor: &ManagedAddress, credit: &BigUint) -> BigUint {
+        let balance = self.depositor_balance(depositor).get();
+        let principal = self.depositor_principal(depositor).get();
+        let remaining_balance = &balance - credit;
+        let remaining_principal = &principal * &remaining_balance / &balance;
+
+        self.depositor_balance(depositor).set(&remaining_balance);
+        self.depositor_principal(depositor)
+            .set(&remaining_principal);
+        if remaining_balance == 0 {
+            self.depositors().remove(depositor);
+            self.depositor_rate(depositor).clear();
+        }
+        self.total_deposited().update(|total| *total -= credit);
+
+        principal - remaining_principal
+    }
+    fn harvest_for(
+        &self,
+        depositor: &ManagedAddress,
+        token: &TokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+    ) -> BigUint {
+        let sc_balance = self.get_harvestable_balance(token, nonce);
+        let dep_balance = self.depositor_balance(depositor).get();
+
+        require!(self.from_tokens().contains(token), "token not supported");
+        require!(nonce == 0 || self.nft_token(token).get(), "fungible only");
+        require!(amount > &0, "Invalid amount");
+        let minimum_harvest = self.minimum_harvest().get();
+        require!(
+            minimum_harvest == 0 || amount >= &minimum_harvest,
+            "harvest below minimum"
+        );
+        require!(sc_balance > 0, "Insufficient contract funds (0)");
+        require!(dep_balance > 0, "Insufficient depositor funds (0)");
+        require!(&sc_balance >= amount, "Insufficient sc funds");
+        let credit_cost = self.harvest_credit_cost(depositor, amount);
+        require!(dep_balance >= credit_cost, "Insufficient depositor funds");
+
+        self.debit_credit(depositor, &credit_cost);
+        let remaining_balance = self.depositor_balance(depositor).get();
+
+        // debit before sending; harvests always use a plain transfer, never the
+        // async swap output path, so a failure reverts the debit with the call
+        self.send().direct(depositor, token, nonce, amount, &[]);
+
+        self.harvest_event(depositor, token, nonce, amount, &remaining_balance);
+        self.record_liquidity_change();
+
+        credit_cost
+    }
+    fn calculate_percentage(&self, total_amount: &BigUint, percentage: &BigUint) -> BigUint {
+        total_amount * percentage / self.get_percentage_base()
+    }
+    fn calculate_swap_fee(&self, amount: &BigUint, percentage: &BigUint) -> BigUint {
+        if !self.round_up_fees().get() {
+            return self.calculate_percentage(amount, percentage);
+        }
+        let base = self.get_percentage_base();
+        (amount * percentage + base - 1u32) / base
+    }
+    fn get_swap_fee_percent(&self, token: &TokenIdentifier) -> BigUint {
+        let token_fee = self.token_fee(token).get();
+        if token_fee > 0 {
+            return BigUint::from(token_fee);
+        }
+        self.fee_percent().get()
+    }
+    // the fee schedule orders tiers by ascending threshold: index 0 starts at
+    // zero with the swap fee, the configured tiers follow; a token fee override
+    // is a schedule of its own with no tiers
+    fn fee_schedule_len(&self, token: &TokenIdentifier) -> usize {
+        if self.token_fee(token).get() > 0 {
+            return 1;
+        }
+        1 + self.fee_tiers().len()
+    }
+    fn fee_schedule_tier(&self, token: &TokenIdentifier, index: usize) -> FeeTier<Self::Api> {
+        if index > 0 {
+            return self.fee_tiers().get(index);
+        }
+        FeeTier {
+            threshold: BigUint::zero(),
+            fee_percent: self
+                .get_swap_fee_percent(token)
+                .to_u64()
+                .unwrap_or_default() as u32,
+        }
+    }
+    fn get_fee_tier(&self, token: &TokenIdentifier, amount: &BigUint) -> FeeTier<Self::Api> {
+        if self.token_fee(token).get() > 0 {
+            return self.fee_schedule_tier(token, 0);
+        }
+        self.global_fee_tier(amount)
+    }
+    fn global_fee_tier(&self, amount: &BigUint) -> FeeTier<Self::Api> {
+        let mut selected = FeeTier {
+            threshold: BigUint::zero(),
+            fee_percent: self.fee_percent().get().to_u64().unwrap_or_default() as u32,
+        };
+        for tier in self.fee_tiers().iter() {
+            if amount < &tier.threshold {
+                break;
+            }
+            selected = tier;
+        }
+        selected
+    }
+    // largest input whose fee-deducted part does not exceed max_after_fee
+    fn max_input_for_fee(&self, max_after_fee: &BigUint, fee_percent: u32) -> Option<BigUint> {
+        let total = self.get_percentage_base();
+        if fee_percent >= total {
+            return None;
+        }
+        let total = BigUint::from(total);
+        let remaining = &total - fee_percent;
+        let max_amount = if self.round_up_fees().get() {
+            ((max_after_fee + 1u32) * &total - 1u32) / remaining
+        } else {
+            max_after_fee * &total / remaining
+        };
+        Some(max_amount)
+    }
+    fn schedule_fee(&self, new_fee_percentage: u32) {
+        self.require_valid_fee_percentage(new_fee_percentage);
+        require!(
+            new_fee_percentage <= self.get_max_fee(),
+            "fee above maximum"
+        );
+        let effective_at = self.blockchain().get_block_timestamp() + self.fee_timelock().get();
+        self.pending_fee().set(&PendingFee {
+            fee_percent: new_fee_percentage,
+            effective_at,
+        });
+    }
+    // only used by init, later changes go through proposeFee/applyFee
+    fn try_set_fee_percentage(&self, new_fee_percentage: u32) {
+        self.require_valid_fee_percentage(new_fee_percentage);
+        require!(
+            new_fee_percentage <= self.get_max_fee(),
+            "fee above maximum"
+        );
+        self.update_fee_percentage(new_fee_percentage);
+    }
+    fn update_fee_percentage(&self, new_fee_percentage: u32) {
+        let old_fee = self.fee_percent().get();
+        let new_fee = BigUint::from(new_fee_percentage);
+        self.previous_fee().set(&old_fee);
+        self.fee_percent().set(&new_fee);
+        self.fee_changed_event(&self.blockchain().get_caller(), &old_fee, &new_fee);
+    }
+    fn require_valid_fee_percentage(&self, fee_percentage: u32) {
+        require!(
+            fee_percentage > 0 && fee_percentage < self.get_percentage_base(),
+            "Invalid percentage value, should be between 0 and the percentage base"
+        );
+    }
+    fn execute_swap(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: &BigUint,
+        min_amount_out: OptionalValue<BigUint>,
+        referrer: OptionalValue<ManagedAddress>,
+    ) {
+        let caller = self.require_can_swap();
+        // a zero referrer stands for none, so later arguments such as the
+        // swap deadline can be passed without naming one
+        let referrer = referrer
+            .into_option()
+            .filter(|referrer| !referrer.is_zero());
+        if let Some(referrer) = &referrer {
+            require!(referrer != &caller, "cannot refer yourself");
+        }
+        let (output_token, fee, amount_out) = self.quote_swap(token_id, amount);
+        if let OptionalValue::Some(min_out) = min_amount_out {
+            require!(amount_out >= min_out, "slippage exceeded");
+        }
+
+        let leg = SwapLeg {
+            token: token_id.clone(),
+            amount: amount.clone(),
+            fee,
+            amount_out: amount_out.clone(),
+        };
+        if self.output_gas_limit().get() > 0 {
+            let swap_id = self.next_pending_swap_id();
+            self.defer_swap(swap_id, &leg);
+            self.send_swap_output_async(swap_id, &caller, &output_token, &amount_out, referrer);
+        }
+
+        // synchronous transfer: if it fails the whole swap reverts, refunding
+        // the payment and undoing the fee accounting
+        self.book_swap(&caller, &leg, &output_token, &referrer);
+        self.send()
+            .direct(&caller, &output_token, 0, &amount_out, &[]);
+
+        self.swap_leg_event(&caller, &leg, &output_token);
+        self.record_liquidity_change();
+    }
+    // with a non-zero gas limit the output can reach recipients that are
+    // contracts; the transfer is asynchronous, so the swap is only booked once
+    // the callback reports it went through and the payments are refunded if not
+    fn send_swap_output_async(
+        &self,
+        swap_id: u64,
+        to: &ManagedAddress,
+        token: &TokenIdentifier,
+        amount: &BigUint,
+        referrer: Option<ManagedAddress>,
+    ) -> ! {
+        require!(
+            self.blockchain().get_gas_left() >= self.output_gas_limit().get(),
+            "insufficient gas for output"
+        );
+
+        self.pending_swap(swap_id).set(&PendingSwap {
+            caller: to.clone(),
+            output_token: token.clone(),
+            referrer,
+        });
+        // call_and_exit never returns to non_reentrant, release the lock here
+        self.locked().clear();
+        self.send()
+            .contract_call::<()>(to.clone(), self.output_endpoint().get())
+            .add_token_transfer(token.clone(), 0, amount.clone())
+            .async_call()
+            .with_callback(self.callbacks().swap_output_callback(swap_id))
+            .call_and_exit()
+    }
+    fn require_can_swap(&self) -> ManagedAddress {
+        require!(!self.swap_paused().get(), "swap is paused");
+        let caller = self.blockchain().get_caller();
+        require!(!caller.is_zero(), "invalid caller");
+        self.require_not_blacklisted(&caller);
+        require!(
+            !self.allowlist_enabled().get() || self.allowed(&caller).get(),
+            "address not allowed"
+        );
+        caller
+    }
+    // validates one payment, returns (output token, fee, amount out)
+    fn quote_swap(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: &BigUint,
+    ) -> (TokenIdentifier, BigUint, BigUint) {
+        require!(
+            amount >= &self.minimum_swap().get(),
+            "swap amount below minimum"
+        );
+        let max_swap = self.max_swap().get();
+        require!(max_swap == 0 || amount <= &max_swap, "amount too large");
+        require!(self.from_tokens().contains(token_id), "token not supported");
+        let output_token = self.get_output_token(token_id);
+        require!(token_id != &output_token, "cannot swap token to itself");
+
+        let (fee, amount_after_fee, amount_out) = self.calculate_swap(token_id, amount);
+        require!(fee > 0, "fee rounds to zero");
+        require!(&amount_after_fee < amount, "incorrect fee");
+        require!(amount_after_fee > 0, "fee consumes the whole amount");
+        require!(
+            amount_out <= self.blockchain().get_sc_balance(&output_token, 0),
+            "no liquidity"
+        );
+        require!(amount_out > 0, "nothing to send");
+
+        (output_token, fee, amount_out)
+    }
+    // holds the payment until the output callback books or refunds it, so it
+    // cannot be harvested away in the meantime
+    fn defer_swap(&self, swap_id: u64, leg: &SwapLeg<Self::Api>) {
+        self.pending_swap_amount(&leg.token)
+            .update(|pending| *pending += &leg.amount);
+        self.pending_swap_legs(swap_id).push(leg);
+    }
+    // a router can run several async swaps in one transaction, so pending
+    // swaps get an id of their own rather than the transaction hash
+    fn next_pending_swap_id(&self) -> u64 {
+        self.last_pending_swap_id().update(|id| {
+            *id += 1;
+            *id
+        })
+    }
+    fn book_swap(
+        &self,
+        caller: &ManagedAddress,
+        leg: &SwapLeg<Self::Api>,
+        output_token: &TokenIdentifier,
+        referrer: &Option<ManagedAddress>,
+    ) {
+        let mut protocol_fee = leg.fee.clone();
+        if let Some(referrer) = referrer {
+            let share = BigUint::from(self.referral_share().get());
+            let referral_fee = self.calculate_percentage(&leg.fee, &share);
+            protocol_fee -= &referral_fee;
+            self.referral_earned(referrer, &leg.token)
+                .update(|earned| *earned += &referral_fee);
+            self.referral_outstanding(&leg.token)
+                .update(|outstanding| *outstanding += &referral_fee);
+        }
+
+        self.accumulated_fees(&leg.token)
+            .update(|accumulated| *accumulated += &protocol_fee);
+        self.swap_count().update(|count| *count += 1);
+        self.swap_volume(&leg.token)
+            .update(|volume| *volume += &leg.amount);
+        self.accrue_rounding_dust(output_token, &(&leg.amount - &leg.fee));
+        self.record_swap(caller, &leg.token, &leg.amount);
+    }
+    fn swap_leg_event(
+        &self,
+        caller: &ManagedAddress,
+        leg: &SwapLeg<Self::Api>,
+        output_token: &TokenIdentifier,
+    ) {
+        self.swap_event(
+            caller,
+            &leg.token,
+            output_token,
+            &leg.amount,
+            &leg.fee,
+            &leg.amount_out,
+        );
+    }
+    fn calculate_swap(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: &BigUint,
+    ) -> (BigUint, BigUint, BigUint) {
+        let fee_percent = BigUint::from(self.get_fee_tier(token_id, amount).fee_percent);
+        let mut fee = self.calculate_swap_fee(amount, &fee_percent);
+        let minimum_fee = self.minimum_fee().get();
+        if fee < minimum_fee {
+            fee = core::cmp::min(minimum_fee, amount.clone());
+        }
+        let amount_after_fee = amount - &fee;
+        let amount_out = self.apply_exchange_rate(&amount_after_fee);
+
+        (fee, amount_after_fee, amount_out)
+    }
+    // the floored output leaves remainder / denominator of an output unit in
+    // the contract; it is collected at RATE_PRECISION resolution and every
+    // whole unit is moved to the output token's accumulated fees
+    fn accrue_rounding_dust(&self, output_token: &TokenIdentifier, amount_after_fee: &BigUint) {
+        let rate = self.current_exchange_rate();
+        let remainder = amount_after_fee * &rate.numerator % &rate.denominator;
+        if remainder == 0 {
+            return;
+        }
+
+        let dust = self.rounding_dust(output_token).update(|dust| {
+            *dust += remainder * RATE_PRECISION / &rate.denominator;
+            dust.clone()
+        });
+        let whole_units = dust / RATE_PRECISION;
+        if whole_units > 0 {
+            self.rounding_dust(output_token)
+                .update(|dust| *dust -= &whole_units * RATE_PRECISION);
+            self.accumulated_fees(output_token)
+                .update(|accumulated| *accumulated += whole_units);
+        }
+    }
+    // ring buffer, once full the oldest entry is overwritten
+    fn record_swap(&self, caller: &ManagedAddress, token: &TokenIdentifier, amount: &BigUint) {
+        let capacity = self.recent_swaps_capacity().get();
+        if capacity == 0 {
+            return;
+        }
+        let record = SwapRecord {
+            caller: caller.clone(),
+            token: token.clone(),
+            amount: amount.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+        };
+        let next = self.recent_swaps_next().get();
+        if self.recent_swaps().len() < capacity {
+            self.recent_swaps().push(&record);
+        } else {
+            self.recent_swaps().set(next + 1, &record);
+        }
+        self.recent_swaps_next().set((next + 1) % capacity);
+    }
+    fn calculate_amount_with_fees(&self, amount: &BigUint) -> BigUint {
+        let fee_percent = self.deposit_fee_percent().get();
+        let fee = self.calculate_percentage(amount, &fee_percent);
+
+        amount + &fee
+    }
+    fn current_exchange_rate(&self) -> ExchangeRate<Self::Api> {
+        if self.exchange_rate().is_empty() {
+            // no rate configured, swaps are 1:1
+            return ExchangeRate {
+                numerator: BigUint::from(1u32),
+                denominator: BigUint::from(1u32),
+            };
+        }
+        self.exchange_rate().get()
+    }
+    fn scaled_exchange_rate(&self) -> BigUint {
+        let rate = self.current_exchange_rate();
+        rate.numerator * RATE_PRECISION / rate.denominator
+    }
+    fn apply_exchange_rate(&self, amount: &BigUint) -> BigUint {
+        let rate = self.current_exchange_rate();
+        amount * &rate.numerator / &rate.denominator
+    }
+    fn require_harvest_cooldown_elapsed(&self, address: &ManagedAddress) {
+        let cooldown = self.harvest_cooldown().get();
+        let last_harvest = self.last_harvest(address).get();
+        if cooldown == 0 || last_harvest == 0 {
+            return;
+        }
+        require!(
+            self.blockchain().get_block_timestamp() >= last_harvest + cooldown,
+            "harvest cooldown active"
+        );
+    }
+    fn non_reentrant<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        require!(!self.locked().get(), "reentrant call");
+        self.locked().set(&true);
+        let result = f();
+        self.locked().clear();
+        result
+    }
+    fn require_within_max_depositor_balance(&self, address: &ManagedAddress, credit: &BigUint) {
+        let max_balance = self.max_depositor_balance().get();
+        if max_balance == 0 {
+            return;
+        }
+        let new_balance = self.depositor_balance(address).get() + credit;
+        require!(new_balance <= max_balance, "max depositor balance exceeded");
+    }
+    // the framework owner until an ownership transfer has been accepted
+    fn get_effective_owner(&self) -> ManagedAddress {
+        if self.owner().is_empty() {
+            return self.blockchain().get_owner_address();
+        }
+        self.owner().get()
+    }
+    fn require_owner(&self) {
+        require!(
+            self.blockchain().get_caller() == self.get_effective_owner(),
+            "Endpoint can only be called by owner"
+        );
+    }
+    fn require_admin(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.get_effective_owner() || self.admins().contains(&caller),
+            "Endpoint can only be called by owner or admin"
+        );
+    }
+    fn require_min_reserve_kept(&self, token: &TokenIdentifier, nonce: u64, amount: &BigUint) {
+        let reserve = self.min_reserve(token).get();
+        require!(
+            self.get_unreserved_balance(token, nonce) >= amount + &reserve,
+            "min reserve breached"
+        );
+    }
+    fn require_from_tokens_capacity(&self, token: &TokenIdentifier) {
+        let max_from_tokens = self.max_from_tokens().get();
+        if max_from_tokens == 0 || self.from_tokens().contains(token) {
+            return;
+        }
+        require!(
+            self.from_tokens().len() < max_from_tokens as usize,
+            "too many from tokens"
+        );
+    }
+    fn require_not_blacklisted(&self, address: &ManagedAddress) {
+        require!(!self.blacklisted(address).get(), "address blacklisted");
+    }
+
+    // OWNER ENDPOINTS
+
+    #[endpoint(proposeNewOwner)]
+    fn propose_new_owner(&self, address: ManagedAddress) {
+        self.require_owner();
+        require!(!address.is_zero(), "invalid owner");
+        self.pending_owner().set(&address);
+    }
+
+    #[endpoint(acceptOwnership)]
+    fn accept_ownership(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.pending_owner().is_empty() && self.pending_owner().get() == caller,
+            "caller is not the pending owner"
+        );
+        self.pending_owner().clear();
+        self.owner().set(&caller);
+    }
+
+    #[endpoint(cancelOwnershipTransfer)]
+    fn cancel_ownership_transfer(&self) {
+        self.require_owner();
+        self.pending_owner().clear();
+    }
+
+    #[endpoint(addFromToken)]
+    fn add_from_token(
+        &self,
+        asset: TokenIdentifier,
+        #[var_args] output_token: OptionalValue<TokenIdentifier>,
+    ) -> () {
+        self.require_admin();
+        require!(asset.is_valid_esdt_identifier(), "Invalid ESDT");
+        self.insert_from_token(asset, output_token);
+    }
+
+    // an empty identifier argument also decodes as EGLD, so accepting EGLD
+    // takes its own endpoint
+    #[endpoint(addEgldFromToken)]
+    fn add_egld_from_token(&self, #[var_args] output_token: OptionalValue<TokenIdentifier>) {
+        self.require_admin();
+        self.insert_from_token(TokenIdentifier::egld(), output_token);
+    }
+
+    #[endpoint(addFromTokens)]
+    fn add_from_tokens(&self, #[var_args] tokens: MultiValueEncoded<TokenIdentifier>) -> u32 {
+        self.require_owner();
+        let mut inserted = 0u32;
+        for token in tokens.into_iter() {
+            require!(token.is_valid_esdt_identifier(), "Invalid ESDT");
+            require!(
+                self.to_token().is_empty() || self.to_token().get() != token,
+                "from and to token must differ"
+            );
+            self.require_from_tokens_capacity(&token);
+            if self.from_tokens().insert(token) {
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
+    #[endpoint(removeFromToken)]
+    fn remove_from_token(&self, asset: TokenIdentifier) -> bool {
+        self.require_owner();
+        self.output_for(&asset).clear();
+        self.from_tokens().remove(&asset)
+    }
+
+    #[endpoint(setMaxFromTokens)]
+    fn set_max_from_tokens(&self, max_from_tokens: u32) {
+        self.require_owner();
+        self.max_from_tokens().set(max_from_tokens);
+    }
+
+    #[endpoint(setNftToken)]
+    fn set_nft_token(&self, token: TokenIdentifier, is_nft: bool) {
+        self.require_owner();
+        self.nft_token(&token).set(is_nft);
+    }
+
+    #[endpoint(setToToken)]
+    fn add_to_token(&self, asset: TokenIdentifier) -> () {
+        self.require_admin();
+        require!(asset.is_valid_esdt_identifier(), "Invalid ESDT");
+        require!(
+            !self.from_tokens().contains(&asset),
+            "from and to token must differ"
+        );
+        if self.to_token().is_empty() {
+            self.to_token().set(&asset);
+            return;
+        }
+
+        // depositor credit and liquidity are denominated in the current to token
+        let old_token = self.to_token().get();
+        require!(self.total_deposited().get() == 0, "outstanding deposits");
+        require!(
+            self.blockchain().get_sc_balance(&old_token, 0) == 0,
+            "to token liquidity remaining"
+        );
+        self.to_token().set(&asset);
+
+        self.to_token_changed_event(&self.blockchain().get_caller(), &old_token, &asset);
+    }
+
+    #[endpoint(addAdmin)]
+    fn add_admin(&self, address: ManagedAddress) {
+        self.require_owner();
+        self.admins().insert(address);
+    }
+
+    #[endpoint(removeAdmin)]
+    fn remove_admin(&self, address: ManagedAddress) {
+        self.require_owner();
+        self.admins().remove(&address);
+    }
+
+    #[endpoint(addToBlacklist)]
+    fn add_to_blacklist(&self, address: ManagedAddress) {
+        self.require_owner();
+        self.blacklisted(&address).set(&true);
+    }
+
+    #[endpoint(removeFromBlacklist)]
+    fn remove_from_blacklist(&self, address: ManagedAddress) {
+        self.require_owner();
+        self.blacklisted(&address).clear();
+    }
+
+    #[endpoint(setAllowlistEnabled)]
+    fn set_allowlist_enabled(&self, enabled: bool) {
+        self.require_owner();
+        self.allowlist_enabled().set(enabled);
+    }
+
+    #[endpoint(addToAllowlist)]
+    fn add_to_allowlist(&self, address: ManagedAddress) {
+        self.require_owner();
+        self.allowed(&address).set(&true);
+    }
+
+    #[endpoint(removeFromAllowlist)]
+    fn remove_from_allowlist(&self, address: ManagedAddress) {
+        self.require_owner();
+        self.allowed(&address).clear();
+    }
+
+    #[endpoint(proposeFee)]
+    fn propose_fee(&self, new_fee_percentage: u32) {
+        self.require_admin();
+        self.schedule_fee(new_fee_percentage);
+    }
+
+    // the fee still goes through the timelock, without one it applies at once
+    #[endpoint(updateConfig)]
+    fn update_config(
+        &self,
+        #[var_args] fee: OptionalValue<u32>,
+        #[var_args] min_deposit: OptionalValue<BigUint>,
+        #[var_args] max_swap: OptionalValue<BigUint>,
+    ) {
+        self.require_owner();
+        if let OptionalValue::Some(fee) = fee {
+            self.schedule_fee(fee);
+            if self.fee_timelock().get() == 0 {
+                self.pending_fee().clear();
+                self.update_fee_percentage(fee);
+            }
+        }
+        if let OptionalValue::Some(min_deposit) = min_deposit {
+            self.set_minimum_deposit(min_deposit);
+        }
+        if let OptionalValue::Some(max_swap) = max_swap {
+            self.set_max_swap(max_swap);
+        }
+    }
+
+    // replaces the tiers, thresholds must be strictly ascending
+    #[endpoint(setFeeTiers)]
+    fn set_fee_tiers(&self, #[var_args] tiers: MultiValueEncoded<FeeTierArg<Self::Api>>) {
+        self.require_owner();
+        self.fee_tiers().clear();
+        let mut previous_threshold = BigUint::zero();
+        for tier in tiers.into_iter() {
+            let (threshold, fee_percent) = tier.into_tuple();
+            require!(
+                threshold > previous_threshold,
+                "tier thresholds must ascend"
+            );
+            self.require_valid_fee_percentage(fee_percent);
+            require!(fee_percent <= self.get_max_fee(), "fee above maximum");
+            previous_threshold = threshold.clone();
+            self.fee_tiers().push(&FeeTier {
+                threshold,
+                fee_percent,
+            });
+        }
+    }
+
+    #[endpoint(clearFeeTiers)]
+    fn clear_fee_tiers(&self) {
+        self.require_owner();
+        self.fee_tiers().clear();
+    }
+
+    #[endpoint(setMaxFee)]
+    fn set_max_fee(&self, max_fee: u32) {
+        self.require_owner();
+        require!(
+            max_fee > 0 && max_fee <= self.get_max_fee_hard_limit(),
+            "max fee above hard limit"
+        );
+        self.max_fee().set(max_fee);
+    }
+
+    #[endpoint(applyFee)]
+    fn apply_fee(&self) {
+        require!(!self.pending_fee().is_empty(), "no pending fee");
+        let pending = self.pending_fee().get();
+        require!(
+            self.blockchain().get_block_timestamp() >= pending.effective_at,
+            "fee timelock active"
+        );
+
+        // the cap may have been lowered while the fee was pending
+        require!(
+            pending.fee_percent <= self.get_max_fee(),
+            "fee above maximum"
+        );
+
+        self.pending_fee().clear();
+        self.update_fee_percentage(pending.fee_percent);
+    }
+
+    #[endpoint(setFeeTimelock)]
+    fn set_fee_timelock(&self, timelock: u64) {
+        self.require_owner();
+        self.fee_timelock().set(timelock);
+    }
+
+    #[endpoint(setDepositFee)]
+    fn set_deposit_fee(&self, fee_percentage: u32) {
+        self.require_owner();
+        self.require_valid_fee_percentage(fee_percentage);
+        self.deposit_fee_percent()
+            .set(&BigUint::from(fee_percentage));
+    }
+
+    #[endpoint(setReferralShare)]
+    fn set_referral_share(&self, share: u32) {
+        self.require_owner();
+        // zero turns referrals off again
+        require!(share < self.get_percentage_base(), "Invalid referral share");
+        self.referral_share().set(share);
+    }
+
+    #[endpoint(setRoundUpFees)]
+    fn set_round_up_fees(&self, round_up: bool) {
+        self.require_owner();
+        self.round_up_fees().set(round_up);
+    }
+
+    #[endpoint(setTokenFee)]
+    fn set_token_fee(&self, token: TokenIdentifier, fee_percentage: u32) {
+        self.require_owner();
+        if fee_percentage == 0 {
+            // zero removes the override, falling back to the global fee
+            self.token_fee(&token).clear();
+            return;
+        }
+        self.require_valid_fee_percentage(fee_percentage);
+        require!(fee_percentage <= self.get_max_fee(), "fee above maximum");
+        self.token_fee(&token).set(&fee_percentage);
+    }
+
+    #[endpoint(setDepositorBalance)]
+    fn set_depositor_balance(&self, address: ManagedAddress, amount: BigUint) {
+        self.require_owner();
+        // the receipts in circulation could no longer be settled against it
+        require!(!self.receipt_active(), "balance backed by receipts");
+        let old_balance = self.depositor_balance(&address).get();
+        self.total_deposited()
+            .update(|total| *total = &*total - &old_balance + &amount);
+        // the corrected balance is unlocked credit, a blended rate would no
+        // longer describe it
+        self.depositor_rate(&address).clear();
+        // a correction never raises what can be reclaimed
+        self.depositor_principal(&address).update(|principal| {
+            if *principal > amount {
+                *principal = amount.clone();
+            }
+        });
+
+        if amount == 0 {
+            self.depositor_balance(&address).clear();
+            self.depositors().remove(&address);
+        } else {
+            self.depositor_balance(&address).set(&amount);
+            self.depositors().insert(address.clone());
+        }
+
+        self.depositor_balance_set_event(&address, &old_balance, &amount);
+    }
+
+    #[endpoint(takeSnapshot)]
+    fn take_snapshot(&self) -> u64 {
+        self.require_owner();
+        let epoch = self.current_epoch().get();
+        for depositor in self.depositors().iter() {
+            let balance = self.depositor_balance(&depositor).get();
+            self.snapshot(epoch, &depositor).set(&balance);
+        }
+        self.current_epoch().set(epoch + 1);
+        epoch
+    }
+
+    #[endpoint(snapshotFees)]
+    fn snapshot_fees(&self) -> u64 {
+        self.require_owner();
+        let epoch = self.fee_snapshot_epoch().get();
+        let to_token = self.to_token().get();
+        let from_tokens = self.from_tokens();
+        for token in from_tokens.iter().chain(core::iter::once(to_token)) {
+            let fees = self.accumulated_fees(&token).get();
+            self.fee_snapshot(epoch, &token).set(&fees);
+        }
+        self.fee_snapshot_epoch().set(epoch + 1);
+        epoch
+    }
+
+    #[endpoint(setMinimumDeposit)]
+    fn set_minimum_deposit(&self, minimum_deposit: BigUint) {
+        self.require_owner();
+        require!(minimum_deposit > 0, "Invalid minimum deposit");
+        self.minimum_deposit().set(&minimum_deposit);
+    }
+
+    #[endpoint(setReceiptToken)]
+    fn set_receipt_token(&self, token: TokenIdentifier) {
+        self.require_owner();
+        require!(token.is_valid_esdt_identifier(), "invalid receipt token");
+        // receipts already in circulation could no longer be returned
+        require!(self.total_deposited().get() == 0, "deposits outstanding");
+        self.receipt_token().set(&token);
+    }
+
+    #[endpoint(setReceiptEnabled)]
+    fn set_receipt_enabled(&self, enabled: bool) {
+        self.require_owner();
+        require!(
+            !enabled || !self.receipt_token().is_empty(),
+            "receipt token not set"
+        );
+        // outstanding credit was never minted receipts it could be settled with;
+        // disabling stays possible so credit is never locked behind receipts
+        require!(
+            !enabled || self.total_deposited().get() == 0,
+            "deposits outstanding"
+        );
+        self.receipt_enabled().set(enabled);
+    }
+
+    // resizing drops the recorded history
+    #[endpoint(setRecentSwapsCapacity)]
+    fn set_recent_swaps_capacity(&self, capacity: usize) {
+        self.require_owner();
+        require!(capacity > 0, "Invalid capacity");
+        self.recent_swaps().clear();
+        self.recent_swaps_next().clear();
+        self.recent_swaps_capacity().set(capacity);
+    }
+
+    #[endpoint(setMinimumFee)]
+    fn set_minimum_fee(&self, minimum_fee: BigUint) {
+        self.require_owner();
+        self.minimum_fee().set(&minimum_fee);
+    }
+
+    #[endpoint(setMinimumHarvest)]
+    fn set_minimum_harvest(&self, minimum_harvest: BigUint) {
+        self.require_owner();
+        self.minimum_harvest().set(&minimum_harvest);
+    }
+
+    #[endpoint(setMaxDepositorBalance)]
+    fn set_max_depositor_balance(&self, max_balance: BigUint) {
+        self.require_owner();
+        self.max_depositor_balance().set(&max_balance);
+    }
+
+    // caps the credit of a single deposit, zero disables the ceiling
+    #[endpoint(setMaxDepositCredit)]
+    fn set_max_deposit_credit(&self, max_credit: BigUint) {
+        self.require_owner();
+        self.max_deposit_credit().set(&max_credit);
+    }
+
+    #[endpoint(setMinimumSwap)]
+    fn set_minimum_swap(&self, minimum_swap: BigUint) {
+        self.require_owner();
+        require!(minimum_swap > 0, "Invalid minimum swap");
+        self.minimum_swap().set(&minimum_swap);
+    }
+
+    #[endpoint(setMaxSwap)]
+    fn set_max_swap(&self, max_swap: BigUint) {
+        self.require_owner();
+        self.max_swap().set(&max_swap);
+    }
+
+    #[endpoint(setExchangeRate)]
+    fn set_exchange_rate(&self, numerator: BigUint, denominator: BigUint) {
+        self.require_owner();
+        require!(numerator > 0, "Invalid exchange rate numerator");
+        require!(denominator > 0, "Invalid exchange rate denominator");
+        self.exchange_rate().set(&ExchangeRate {
+            numerator,
+            denominator,
+        });
+    }
+
+    #[endpoint(setFeeRecipient)]
+    fn set_fee_recipient(&self, recipient: ManagedAddress) {
+        self.require_owner();
+        require!(!recipient.is_zero(), "invalid fee recipient");
+        self.fee_recipient().set(&recipient);
+    }
+
+    #[endpoint(setHarvestCooldown)]
+    fn set_harvest_cooldown(&self, cooldown_seconds: u64) {
+        self.require_owner();
+        self.harvest_cooldown().set(&cooldown_seconds);
+    }
+
+    // non-zero sends swap outputs through an async call, a failed transfer is
+    // refunded by swap_output_callback
+    #[endpoint(setOutputGasLimit)]
+    fn set_output_gas_limit(&self, gas_limit: u64) {
+        self.require_owner();
+        self.output_gas_limit().set(&gas_limit);
+    }
+
+    // called on the output recipient when the output gas limit is set, empty
+    // for a plain transfer
+    #[endpoint(setOutputEndpoint)]
+    fn set_output_endpoint(&self, endpoint: ManagedBuffer) {
+        self.require_owner();
+        self.output_endpoint().set(&endpoint);
+    }
+
+    #[endpoint(withdraw)]
+    fn withdraw(&self, token: TokenIdentifier, nonce: u64) -> () {
+        self.require_owner();
+        let owner = self.get_effective_owner();
+        // everything above the reserve
+        let amount = self.get_withdrawable_by_owner(token.clone(), nonce);
+        require!(amount > 0, "nothing to withdraw");
+        self.send().direct(&owner, &token, nonce, &amount, &[]);
+
+        self.withdraw_event(&owner, &token, nonce, &amount);
+        self.record_liquidity_change();
+    }
+
+    #[endpoint(setMinReserve)]
+    fn set_min_reserve(&self, token: TokenIdentifier, amount: BigUint) {
+        self.require_owner();
+        self.min_reserve(&token).set(&amount);
+    }
+
+    // pushes harvestable tokens to a depositor during migrations, the pause and
+    // cooldown do not apply but every balance check of harvest does
+    #[endpoint(forceHarvest)]
+    fn force_harvest(
+        &self,
+        depositor: ManagedAddress,
+        token: TokenIdentifier,
+        nonce: u64,
+        amount: BigUint,
+    ) {
+        self.require_owner();
+        self.non_reentrant(|| {
+            require!(!depositor.is_zero(), "invalid depositor");
+            require!(
+                !self.receipt_active(),
+                "receipts must be returned by the depositor"
+            );
+
+            self.harvest_for(&depositor, &token, nonce, &amount);
+        })
+    }
+
+    // ignores min_reserve, meant for incidents
+    #[endpoint(emergencyWithdrawAll)]
+    fn emergency_withdraw_all(
+        &self,
+        #[var_args] tokens: MultiValueEncoded<MultiValue2<TokenIdentifier, u64>>,
+    ) {
+        self.require_owner();
+        let owner = self.get_effective_owner();
+        for entry in tokens.into_iter() {
+            let (token, nonce) = entry.into_tuple();
+            let amount = self.blockchain().get_sc_balance(&token, nonce);
+            if amount == 0 {
+                continue;
+            }
+            self.send().direct(&owner, &token, nonce, &amount, &[]);
+
+            self.withdraw_event(&owner, &token, nonce, &amount);
+            self.record_liquidity_change();
+        }
+    }
+
+    #[endpoint(sweepUnsupportedToken)]
+    fn sweep_unsupported_token(&self, token: TokenIdentifier, to: ManagedAddress) {
+        self.require_owner();
+        require!(!to.is_zero(), "invalid address");
+        require!(
+            !self.is_contract_token(&token),
+            "cannot sweep a supported token"
+        );
+        let amount = self.blockchain().get_sc_balance(&token, 0);
+        require!(amount > 0, "nothing to sweep");
+        self.require_min_reserve_kept(&token, 0, &amount);
+        self.send().direct(&to, &token, 0, &amount, &[]);
+
+        self.withdraw_event(&to, &token, 0, &amount);
+    }
+
+    #[endpoint(rescueEgld)]
+    fn rescue_egld(&self, to: ManagedAddress, amount: BigUint) {
+        self.require_owner();
+        require!(!to.is_zero(), "invalid address");
+        require!(amount > 0, "Invalid amount");
+        let egld = TokenIdentifier::egld();
+        // swapped EGLD backs fees and referral earnings
+        require!(
+            !self.from_tokens().contains(&egld),
+            "cannot rescue a supported token"
+        );
+        require!(
+            amount <= self.blockchain().get_sc_balance(&egld, 0),
+            "Insufficient sc funds"
+        );
+        self.require_min_reserve_kept(&egld, 0, &amount);
+        self.send().direct_egld(&to, &amount, &[]);
+
+        self.withdraw_event(&to, &egld, 0, &amount);
+    }
+
+    #[endpoint(withdrawAmount)]
+    fn withdraw_amount(&self, token: TokenIdentifier, nonce: u64, amount: BigUint) {
+        self.require_owner();
+        require!(amount > 0, "Invalid amount");
+        require!(
+            amount <= self.blockchain().get_sc_balance(&token, nonce),
+            "Insufficient sc funds"
+        );
+        self.require_min_reserve_kept(&token, nonce, &amount);
+
+        let owner = self.get_effective_owner();
+        self.send().direct(&owner, &token, nonce, &amount, &[]);
+
+        self.withdraw_event(&owner, &token, nonce, &amount);
+        self.record_liquidity_change();
+    }
+
+    #[endpoint(pause)]
+    fn pause(&self) {
+        self.require_owner();
+        self.set_paused(true);
+    }
+
+    #[endpoint(unpause)]
+    fn unpause(&self) {
+        self.require_owner();
+        self.set_paused(false);
+    }
+
+    fn set_paused(&self, paused: bool) {
+        self.swap_paused().set(paused);
+        self.deposit_paused().set(paused);
+        self.harvest_paused().set(paused);
+    }
+
+    #[endpoint(setSwapPaused)]
+    fn set_swap_paused(&self, paused: bool) {
+        self.require_owner();
+        self.swap_paused().set(paused);
+    }
+
+    #[endpoint(setDepositPaused)]
+    fn set_deposit_paused(&self, paused: bool) {
+        self.require_owner();
+        self.deposit_paused().set(paused);
+    }
+
+    // also covers reclaimDeposit, which pays out like a harvest
+    #[endpoint(setHarvestPaused)]
+    fn set_harvest_paused(&self, paused: bool) {
+        self.require_owner();
+        self.harvest_paused().set(paused);
+    }
+
+    // CALLBACKS
+
+    // books the swap once its output arrived, refunds the payments otherwise
+    #[callback]
+    fn swap_output_callback(
+        &self,
+        swap_id: u64,
+        #[call_result] result: ManagedAsyncCallResult<IgnoreValue>,
+    ) {
+        let pending = self.pending_swap(swap_id).get();
+        let delivered = matches!(result, ManagedAsyncCallResult::Ok(_));
+        for leg in self.pending_swap_legs(swap_id).iter() {
+            self.pending_swap_amount(&leg.token)
+                .update(|amount| *amount -= &leg.amount);
+            if delivered {
+                self.book_swap(
+                    &pending.caller,
+                    &leg,
+                    &pending.output_token,
+                    &pending.referrer,
+                );
+                self.swap_leg_event(&pending.caller, &leg, &pending.output_token);
+            } else {
+                self.send()
+                    .direct(&pending.caller, &leg.token, 0, &leg.amount, &[]);
+                self.swap_refund_event(&pending.caller, &leg.token, &leg.amount);
+            }
+        }
+        self.pending_swap_legs(swap_id).clear();
+        self.pending_swap(swap_id).clear();
+        self.record_liquidity_change();
+    }
+
+    // EVENTS
+
+    #[event("swap")]
+    fn swap_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] from_token: &TokenIdentifier,
+        #[indexed] to_token: &TokenIdentifier,
+        #[indexed] amount: &BigUint,
+        #[indexed] fee: &BigUint,
+        amount_out: &BigUint,
+    );
+
+    #[event("swap_refund")]
+    fn swap_refund_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] token: &TokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("fee_changed")]
+    fn fee_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_fee: &BigUint,
+        new_fee: &BigUint,
+    );
+
+    #[event("deposit")]
+    fn deposit_event(
+        &self,
+        #[indexed] depositor: &ManagedAddress,
+        #[indexed] token: &TokenIdentifier,
+        #[indexed] amount: &BigUint,
+        amount_with_fees: &BigUint,
+    );
+
+    #[event("harvest")]
+    fn harvest_event(
+        &self,
+        #[indexed] depositor: &ManagedAddress,
+        #[indexed] token: &TokenIdentifier,
+        #[indexed] nonce: u64,
+        #[indexed] amount: &BigUint,
+        remaining_balance: &BigUint,
+    );
+
+    #[event("reclaim_deposit")]
+    fn reclaim_deposit_event(
+        &self,
+        #[indexed] depositor: &ManagedAddress,
+        #[indexed] token: &TokenIdentifier,
+        #[indexed] amount: &BigUint,
+        remaining_balance: &BigUint,
+    );
+
+    #[event("depositor_balance_set")]
+    fn depositor_balance_set_event(
+        &self,
+        #[indexed] depositor: &ManagedAddress,
+        #[indexed] old_balance: &BigUint,
+        new_balance: &BigUint,
+    );
+
+    #[event("to_token_changed")]
+    fn to_token_changed_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] old_token: &TokenIdentifier,
+        new_token: &TokenIdentifier,
+    );
+
+    #[event("liquidity_changed")]
+    fn liquidity_changed_event(&self, #[indexed] token: &TokenIdentifier, liquidity: &BigUint);
+
+    #[event("withdraw")]
+    fn withdraw_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] token: &TokenIdentifier,
+        #[indexed] nonce: u64,
+        amount: &BigUint,
+    );
+
+    // STORAGE
+    #[view(getFeeTiers)]
+    #[storage_mapper("fee_tiers")]
+    fn fee_tiers(&self) -> VecMapper<FeeTier<Self::Api>>;
+
+    #[storage_mapper("max_fee")]
+    fn max_fee(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("percentage_base")]
+    fn percentage_base(&self) -> SingleValueMapper<u32>;
+
+    #[view(getFee)]
+    #[storage_mapper("fee_percent")]
+    fn fee_percent(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getDepositFee)]
+    #[storage_mapper("deposit_fee_percent")]
+    fn deposit_fee_percent(&self) -> SingleValueMapper<BigUint>;
+
+    // part of the swap fee paid to referrers, in parts of the percentage base
+    #[view(getReferralShare)]
+    #[storage_mapper("referral_share")]
+    fn referral_share(&self) -> SingleValueMapper<u32>;
+
+    #[view(getReferralEarned)]
+    #[storage_mapper("referral_earned")]
+    fn referral_earned(
+        &self,
+        addr: &ManagedAddress,
+        token: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    // unclaimed referral earnings of all referrers
+    #[view(getReferralOutstanding)]
+    #[storage_mapper("referral_outstanding")]
+    fn referral_outstanding(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("pending_fee")]
+    fn pending_fee(&self) -> SingleValueMapper<PendingFee>;
+
+    #[view(getFeeTimelock)]
+    #[storage_mapper("fee_timelock")]
+    fn fee_timelock(&self) -> SingleValueMapper<u64>;
+
+    #[view(isRoundUpFees)]
+    #[storage_mapper("round_up_fees")]
+    fn round_up_fees(&self) -> SingleValueMapper<bool>;
+
+    #[view(getPreviousFee)]
+    #[storage_mapper("previous_fee")]
+    fn previous_fee(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getTokenFee)]
+    #[storage_mapper("token_fee")]
+    fn token_fee(&self, token: &TokenIdentifier) -> SingleValueMapper<u32>;
+
+    #[view(getMaxFromTokens)]
+    #[storage_mapper("max_from_tokens")]
+    fn max_from_tokens(&self) -> SingleValueMapper<u32>;
+
+    #[view(isNftToken)]
+    #[storage_mapper("nft_token")]
+    fn nft_token(&self, token: &TokenIdentifier) -> SingleValueMapper<bool>;
+
+    #[view(getToToken)]
+    #[storage_mapper("to_token")]
+    fn to_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getReceiptToken)]
+    #[storage_mapper("receipt_token")]
+    fn receipt_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(isReceiptEnabled)]
+    #[storage_mapper("receipt_enabled")]
+    fn receipt_enabled(&self) -> SingleValueMapper<bool>;
+
+    #[view(getVersion)]
+    #[storage_mapper("version")]
+    fn version(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getMinimumDeposit)]
+    #[storage_mapper("minimum_deposit")]
+    fn minimum_deposit(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMinimumFee)]
+    #[storage_mapper("minimum_fee")]
+    fn minimum_fee(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMinimumHarvest)]
+    #[storage_mapper("minimum_harvest")]
+    fn minimum_harvest(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMaxDepositCredit)]
+    #[storage_mapper("max_deposit_credit")]
+    fn max_deposit_credit(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMaxDepositorBalance)]
+    #[storage_mapper("max_depositor_balance")]
+    fn max_depositor_balance(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMinimumSwap)]
+    #[storage_mapper("minimum_swap")]
+    fn minimum_swap(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("output_for")]
+    fn output_for(&self, from_token: &TokenIdentifier) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getMaxSwap)]
+    #[storage_mapper("max_swap")]
+    fn max_swap(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getFromTokens)]
+    #[storage_mapper("from_tokens")]
+    fn from_tokens(&self) -> SetMapper<TokenIdentifier>;
+
+    #[view(getBalance)]
+    #[storage_mapper("depositor_balance")]
+    fn depositor_balance(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    // to_token paid in for the credit still held, the most reclaimDeposit returns
+    #[view(getDepositorPrincipal)]
+    #[storage_mapper("depositor_principal")]
+    fn depositor_principal(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("exchange_rate")]
+    fn exchange_rate(&self) -> SingleValueMapper<ExchangeRate<Self::Api>>;
+
+    #[view(getAccumulatedFees)]
+    #[storage_mapper("accumulated_fees")]
+    fn accumulated_fees(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getRoundingDust)]
+    #[storage_mapper("rounding_dust")]
+    fn rounding_dust(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getFeeRecipient)]
+    #[storage_mapper("fee_recipient")]
+    fn fee_recipient(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getMinReserve)]
+    #[storage_mapper("min_reserve")]
+    fn min_reserve(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getOutputGasLimit)]
+    #[storage_mapper("output_gas_limit")]
+    fn output_gas_limit(&self) -> SingleValueMapper<u64>;
+
+    #[view(getOutputEndpoint)]
+    #[storage_mapper("output_endpoint")]
+    fn output_endpoint(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    // swaps sent through an async call, kept until the callback books or
+    // refunds them
+    #[storage_mapper("pending_swap")]
+    fn pending_swap(&self, swap_id: u64) -> SingleValueMapper<PendingSwap<Self::Api>>;
+
+    #[storage_mapper("pending_swap_legs")]
+    fn pending_swap_legs(&self, swap_id: u64) -> VecMapper<SwapLeg<Self::Api>>;
+
+    #[storage_mapper("last_pending_swap_id")]
+    fn last_pending_swap_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getPendingSwapAmount)]
+    #[storage_mapper("pending_swap_amount")]
+    fn pending_swap_amount(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getHarvestCooldown)]
+    #[storage_mapper("harvest_cooldown")]
+    fn harvest_cooldown(&self) -> SingleValueMapper<u64>;
+
+    #[view(getHarvestAllowance)]
+    #[storage_mapper("harvest_allowance")]
+    fn harvest_allowance(
+        &self,
+        owner: &ManagedAddress,
+        spender: &ManagedAddress,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getLastHarvest)]
+    #[storage_mapper("last_harvest")]
+    fn last_harvest(&self, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("recent_swaps")]
+    fn recent_swaps(&self) -> VecMapper<SwapRecord<Self::Api>>;
+
+    #[storage_mapper("recent_swaps_next")]
+    fn recent_swaps_next(&self) -> SingleValueMapper<usize>;
+
+    #[view(getRecentSwapsCapacity)]
+    #[storage_mapper("recent_swaps_capacity")]
+    fn recent_swaps_capacity(&self) -> SingleValueMapper<usize>;
+
+    #[view(getLiquidityHighWaterMark)]
+    #[storage_mapper("liquidity_high_water_mark")]
+    fn liquidity_high_water_mark(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getSwapCount)]
+    #[storage_mapper("swap_count")]
+    fn swap_count(&self) -> SingleValueMapper<u64>;
+
+    #[view(getSwapVolume)]
+    #[storage_mapper("swap_volume")]
+    fn swap_volume(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalDeposited)]
+    #[storage_mapper("total_deposited")]
+    fn total_deposited(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getSnapshotBalance)]
+    #[storage_mapper("snapshot")]
+    fn snapshot(&self, epoch: u64, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getCurrentEpoch)]
+    #[storage_mapper("current_epoch")]
+    fn current_epoch(&self) -> SingleValueMapper<u64>;
+
+    #[view(getFeeSnapshot)]
+    #[storage_mapper("fee_snapshot")]
+    fn fee_snapshot(&self, epoch: u64, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getFeeSnapshotEpoch)]
+    #[storage_mapper("fee_snapshot_epoch")]
+    fn fee_snapshot_epoch(&self) -> SingleValueMapper<u64>;
+
+    #[view(getDepositorRate)]
+    #[storage_mapper("depositor_rate")]
+    fn depositor_rate(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("depositors")]
+    fn depositors(&self) -> SetMapper<ManagedAddress>;
+
+    #[storage_mapper("locked")]
+    fn locked(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("owner")]
+    fn owner(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("pending_owner")]
+    fn pending_owner(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getAdmins)]
+    #[storage_mapper("admins")]
+    fn admins(&self) -> SetMapper<ManagedAddress>;
+
+    #[view(isBlacklisted)]
+    #[storage_mapper("blacklisted")]
+    fn blacklisted(&self, address: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[view(isAllowlistEnabled)]
+    #[storage_mapper("allowlist_enabled")]
+    fn allowlist_enabled(&self) -> SingleValueMapper<bool>;
+
+    #[view(isAllowed)]
+    #[storage_mapper("allowed")]
+    fn allowed(&self, address: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[view(isSwapPaused)]
+    #[storage_mapper("swap_paused")]
+    fn swap_paused(&self) -> SingleValueMapper<bool>;
+
+    #[view(isDepositPaused)]
+    #[storage_mapper("deposit_paused")]
+    fn deposit_paused(&self) -> SingleValueMapper<bool>;
+
+    #[view(isHarvestPaused)]
+    #[storage_mapper("harvest_paused")]
+    fn harvest_paused(&self) -> SingleValueMapper<bool>;
 }
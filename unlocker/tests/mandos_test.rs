@@ -1,4 +1,3 @@
-use elrond_wasm::*;
 use elrond_wasm_debug::*;
 
 fn contract_map() -> BlockchainMock {
@@ -51,3 +50,575 @@ fn unlocker_balances_rs() {
 fn unlocker_harvest_rs() {
     elrond_wasm_debug::mandos_rs("mandos/unlocker-harvest.scen.json", contract_map());
 }
+
+#[test]
+fn unlocker_remove_from_token_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-remove-from-token.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_swap_event_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-swap-event.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_events_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-events.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_pause_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-pause.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_minimum_deposit_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-minimum-deposit.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_exchange_rate_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-exchange-rate.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_swap_slippage_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-swap-slippage.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_accumulated_fees_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-accumulated-fees.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_withdraw_amount_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-withdraw-amount.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_claim_fees_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-claim-fees.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_harvest_cooldown_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-harvest-cooldown.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_reclaim_deposit_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-reclaim-deposit.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_distinct_tokens_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-distinct-tokens.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_depositors_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-depositors.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_minimum_swap_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-minimum-swap.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_token_fee_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-token-fee.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_reentrancy_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-reentrancy.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_output_tokens_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-output-tokens.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_max_depositor_balance_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-max-depositor-balance.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_swap_quote_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-swap-quote.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_harvest_multi_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-harvest-multi.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_blacklist_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-blacklist.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_max_swap_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-max-swap.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_total_deposited_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-total-deposited.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_admins_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-admins.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_fee_changed_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-fee-changed.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_emergency_withdraw_all_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-emergency-withdraw-all.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_contract_state_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-contract-state.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_egld_swap_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-egld-swap.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_required_deposit_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-required-deposit.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_upgrade_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-upgrade.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_harvest_fungible_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-harvest-fungible.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_dust_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-dust.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_add_from_tokens_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-add-from-tokens.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_deposit_fee_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-deposit-fee.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_max_swapable_amount_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-max-swapable-amount.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_set_depositor_balance_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-set-depositor-balance.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_pause_per_endpoint_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-pause-per-endpoint.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_swap_to_itself_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-swap-to-itself.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_fee_breakdown_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-fee-breakdown.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_min_reserve_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-min-reserve.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_depositor_share_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-depositor-share.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_referral_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-referral.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_claim_referral_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-claim-referral.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_fee_timelock_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-fee-timelock.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_harvestable_amount_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-harvestable-amount.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_deposit_for_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-deposit-for.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_round_up_fees_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-round-up-fees.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_from_tokens_with_fees_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-from-tokens-with-fees.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_snapshot_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-snapshot.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_liquidity_after_rate_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-liquidity-after-rate.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_ownership_transfer_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-ownership-transfer.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_swap_counters_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-swap-counters.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_balance_batch_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-balance-batch.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_burn_fees_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-burn-fees.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_max_from_tokens_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-max-from-tokens.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_effective_fee_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-effective-fee.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_deposit_with_rate_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-deposit-with-rate.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_sweep_unsupported_token_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-sweep-unsupported-token.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_token_balance_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-token-balance.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_minimum_harvest_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-minimum-harvest.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_local_burn_role_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-local-burn-role.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_swap_partial_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-swap-partial.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_version_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-version.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_deposit_receipt_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-deposit-receipt.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_all_accumulated_fees_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-all-accumulated-fees.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_swap_deadline_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-swap-deadline.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_depositor_count_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-depositor-count.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_minimum_fee_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-minimum-fee.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_time_until_next_harvest_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-time-until-next-harvest.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_allowlist_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-allowlist.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_protocol_health_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-protocol-health.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_deposit_batch_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-deposit-batch.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_rounding_dust_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-rounding-dust.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_to_token_migration_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-to-token-migration.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_recent_swaps_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-recent-swaps.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_force_harvest_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-force-harvest.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_percentage_base_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-percentage-base.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_token_support_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-token-support.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_max_fee_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-max-fee.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_fee_tiers_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-fee-tiers.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_harvest_ordering_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-harvest-ordering.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_balance_in_to_token_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-balance-in-to-token.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_update_config_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-update-config.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_init_parameters_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-init-parameters.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_deposit_ceiling_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-deposit-ceiling.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_can_swap_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-can-swap.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_rescue_egld_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-rescue-egld.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_withdrawable_by_owner_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-withdrawable-by-owner.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_liquidity_changed_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-liquidity-changed.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_close_account_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-close-account.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_fee_revenue_between_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-fee-revenue-between.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_swap_multi_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-swap-multi.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_output_gas_limit_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-output-gas-limit.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_deposit_fungible_only_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-deposit-fungible-only.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_required_liquidity_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-required-liquidity.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_harvest_allowance_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-harvest-allowance.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_contract_owner_rs() {
+    elrond_wasm_debug::mandos_rs("mandos/unlocker-contract-owner.scen.json", contract_map());
+}
+
+#[test]
+fn unlocker_swap_output_refund_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-swap-output-refund.scen.json",
+        contract_map(),
+    );
+}
+
+#[test]
+fn unlocker_harvest_attributed_rs() {
+    elrond_wasm_debug::mandos_rs(
+        "mandos/unlocker-harvest-attributed.scen.json",
+        contract_map(),
+    );
+}
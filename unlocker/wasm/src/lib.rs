@@ -7,18 +7,171 @@
 elrond_wasm_node::wasm_endpoints! {
     unlocker
     (
+        acceptOwnership
+        addAdmin
+        addEgldFromToken
         addFromToken
+        addFromTokens
+        addToAllowlist
+        addToBlacklist
+        applyFee
+        approveHarvest
+        burnFees
+        canSwap
+        cancelOwnershipTransfer
+        claimFees
+        claimReferral
+        clearFeeTiers
+        closeAccount
         deposit
+        depositBatch
+        depositFor
+        depositWithRate
+        emergencyWithdrawAll
+        forceHarvest
+        getAccumulatedFees
+        getAdmins
+        getAllAccumulatedFees
         getBalance
+        getBalanceBatch
+        getContractOwner
+        getContractState
+        getCurrentEpoch
+        getDepositFee
+        getDepositorBalanceInToToken
+        getDepositorCount
+        getDepositorPrincipal
+        getDepositorRate
+        getDepositorShare
+        getDepositors
+        getDust
+        getEffectiveFee
+        getExchangeRate
         getFee
+        getFeeBreakdown
+        getFeeRecipient
+        getFeeRevenueBetween
+        getFeeSnapshot
+        getFeeSnapshotEpoch
+        getFeeTiers
+        getFeeTimelock
         getFromTokens
+        getFromTokensWithFees
+        getHarvestAllowance
+        getHarvestCooldown
+        getHarvestableAmount
+        getInitParameters
+        getLastHarvest
         getLiquidityBalance
+        getLiquidityHighWaterMark
+        getMaxDepositCredit
+        getMaxDepositorBalance
+        getMaxFee
+        getMaxFeeHardLimit
+        getMaxFromTokens
+        getMaxSwap
+        getMaxSwapableAmount
+        getMinReserve
+        getMinimumDeposit
+        getMinimumFee
+        getMinimumHarvest
+        getMinimumSwap
+        getOutputEndpoint
+        getOutputGasLimit
+        getOutputToken
+        getPendingFee
+        getPendingOwner
+        getPendingSwapAmount
+        getPercentageBase
+        getPreviousFee
+        getProtocolHealth
+        getReceiptToken
+        getRecentSwaps
+        getRecentSwapsCapacity
+        getReferralEarned
+        getReferralOutstanding
+        getReferralShare
+        getRequiredDepositForTarget
+        getRequiredLiquidityForVolume
+        getRoundingDust
+        getSnapshotBalance
+        getSwapCount
+        getSwapQuote
+        getSwapVolume
+        getTierForAmount
+        getTimeUntilNextHarvest
+        getToToken
+        getTokenBalance
+        getTokenFee
+        getTotalDeposited
+        getVersion
+        getWithdrawableByOwner
         harvest
-        setFee
+        harvestFrom
+        harvestMulti
+        hasLocalBurnRole
+        hasLocalMintRole
+        isAllowed
+        isAllowlistEnabled
+        isBlacklisted
+        isDepositPaused
+        isFromTokenSupported
+        isHarvestPaused
+        isNftToken
+        isPaused
+        isReceiptEnabled
+        isRoundUpFees
+        isSwapPaused
+        pause
+        proposeFee
+        proposeNewOwner
+        reclaimDeposit
+        removeAdmin
+        removeFromAllowlist
+        removeFromBlacklist
+        removeFromToken
+        rescueEgld
+        setAllowlistEnabled
+        setDepositFee
+        setDepositPaused
+        setDepositorBalance
+        setExchangeRate
+        setFeeRecipient
+        setFeeTiers
+        setFeeTimelock
+        setHarvestCooldown
+        setHarvestPaused
+        setMaxDepositCredit
+        setMaxDepositorBalance
+        setMaxFee
+        setMaxFromTokens
+        setMaxSwap
+        setMinReserve
+        setMinimumDeposit
+        setMinimumFee
+        setMinimumHarvest
+        setMinimumSwap
+        setNftToken
+        setOutputEndpoint
+        setOutputGasLimit
+        setReceiptEnabled
+        setReceiptToken
+        setRecentSwapsCapacity
+        setReferralShare
+        setRoundUpFees
+        setSwapPaused
         setToToken
+        setTokenFee
+        snapshotFees
         swap
+        swapMulti
+        swapPartial
+        sweepUnsupportedToken
+        takeSnapshot
+        unpause
+        updateConfig
         withdraw
+        withdrawAmount
+        callBack
     )
 }
-
-elrond_wasm_node::wasm_empty_callback! {}